@@ -0,0 +1,65 @@
+//! Benchmarks the interpreter's hot path: fetch/decode/execute via `tick()`.
+
+use chip8::asm::assemble;
+use chip8::Chip8;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+/// A tight loop touching arithmetic, a conditional skip, and a draw each pass,
+/// representative of the opcode mix a typical ROM exercises per frame.
+fn workload_rom() -> Vec<u8> {
+    assemble(
+        "
+        loop:
+            LD V0, 1
+            ADD V1, V0
+            SE V1, 0
+            LD I, 0
+            DRW V0, V1, 1
+            JP loop
+        ",
+    )
+    .unwrap()
+}
+
+fn bench_tick(c: &mut Criterion) {
+    let rom = workload_rom();
+    c.bench_function("tick", |b| {
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        b.iter(|| {
+            chip8.tick();
+            black_box(chip8.i_register());
+        });
+    });
+}
+
+/// Isolates `Dxyn`'s fast path: a fully on-screen, non-wrapping 15-byte sprite
+/// redrawn every iteration, the shape most draw-heavy ROMs spend their time on.
+fn bench_draw_sprite(c: &mut Criterion) {
+    let rom = assemble(
+        "
+        LD I, sprite
+        LD V0, 10
+        LD V1, 10
+        loop:
+            DRW V0, V1, 15
+            JP loop
+        sprite:
+            DB 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF
+            DB 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF
+        ",
+    )
+    .unwrap();
+    c.bench_function("draw_sprite", |b| {
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        b.iter(|| {
+            chip8.tick();
+            black_box(chip8.last_collision_count());
+        });
+    });
+}
+
+criterion_group!(benches, bench_tick, bench_draw_sprite);
+criterion_main!(benches);
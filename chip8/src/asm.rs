@@ -0,0 +1,369 @@
+//! A small two-pass assembler for the standard CHIP-8 mnemonics.
+//!
+//! Supports labels for `JP`/`CALL` targets and a `DB` directive for raw
+//! bytes, so small test ROMs can be written by hand instead of poked in as
+//! raw opcodes.
+
+use std::collections::HashMap;
+use std::fmt;
+
+const START_ADDR: u16 = 0x200;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum AsmError {
+    UnknownMnemonic(String),
+    UnknownRegister(String),
+    BadOperand(String),
+    WrongOperandCount { mnemonic: String, expected: usize, found: usize },
+    UndefinedLabel(String),
+    DuplicateLabel(String),
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsmError::UnknownMnemonic(m) => write!(f, "unknown mnemonic: {m}"),
+            AsmError::UnknownRegister(r) => write!(f, "unknown register: {r}"),
+            AsmError::BadOperand(o) => write!(f, "bad operand: {o}"),
+            AsmError::WrongOperandCount { mnemonic, expected, found } => write!(
+                f,
+                "{mnemonic} expects {expected} operand(s), found {found}"
+            ),
+            AsmError::UndefinedLabel(l) => write!(f, "undefined label: {l}"),
+            AsmError::DuplicateLabel(l) => write!(f, "duplicate label: {l}"),
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+enum Item {
+    Instruction { mnemonic: String, operands: Vec<String> },
+    Bytes(Vec<u8>),
+}
+
+struct Line {
+    item: Item,
+}
+
+/// Assemble CHIP-8 source into a ROM ready to be passed to [`Chip8::load`](crate::Chip8::load).
+pub fn assemble(source: &str) -> Result<Vec<u8>, AsmError> {
+    let mut labels: HashMap<String, u16> = HashMap::new();
+    let mut lines: Vec<Line> = Vec::new();
+    let mut addr = START_ADDR;
+
+    // Pass 1: strip comments/whitespace, record label addresses, size each item.
+    for raw_line in source.lines() {
+        let mut text = match raw_line.find(';') {
+            Some(idx) => &raw_line[..idx],
+            None => raw_line,
+        }
+        .trim();
+
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim().to_string();
+            if labels.insert(label.clone(), addr).is_some() {
+                return Err(AsmError::DuplicateLabel(label));
+            }
+            text = text[colon + 1..].trim();
+        }
+
+        if text.is_empty() {
+            continue;
+        }
+
+        let mut parts = text.splitn(2, char::is_whitespace);
+        let mnemonic = parts.next().unwrap_or("").to_string();
+        let rest = parts.next().unwrap_or("").trim();
+
+        if mnemonic.eq_ignore_ascii_case("DB") {
+            let bytes = rest
+                .split(',')
+                .map(|s| parse_byte(s.trim()))
+                .collect::<Result<Vec<u8>, AsmError>>()?;
+            addr += bytes.len() as u16;
+            lines.push(Line { item: Item::Bytes(bytes) });
+            continue;
+        }
+
+        let operands = if rest.is_empty() {
+            Vec::new()
+        } else {
+            rest.split(',').map(|s| s.trim().to_string()).collect()
+        };
+        lines.push(Line { item: Item::Instruction { mnemonic, operands } });
+        addr += 2;
+    }
+
+    // Pass 2: encode each item now that every label address is known.
+    let mut rom = Vec::new();
+    for line in lines {
+        match line.item {
+            Item::Bytes(bytes) => rom.extend(bytes),
+            Item::Instruction { mnemonic, operands } => {
+                let opcode = encode(&mnemonic, &operands, &labels)?;
+                rom.push((opcode >> 8) as u8);
+                rom.push((opcode & 0xFF) as u8);
+            }
+        }
+    }
+
+    Ok(rom)
+}
+
+fn parse_register(operand: &str) -> Result<u16, AsmError> {
+    let operand = operand.trim();
+    if operand.len() >= 2 && operand.as_bytes()[0].eq_ignore_ascii_case(&b'V') {
+        if let Ok(v) = u16::from_str_radix(&operand[1..], 16) {
+            if v <= 0xF {
+                return Ok(v);
+            }
+        }
+    }
+    Err(AsmError::UnknownRegister(operand.to_string()))
+}
+
+fn parse_number(operand: &str) -> Option<u16> {
+    let operand = operand.trim();
+    if let Some(hex) = operand.strip_prefix("0x").or(operand.strip_prefix("0X")) {
+        u16::from_str_radix(hex, 16).ok()
+    } else {
+        operand.parse::<u16>().ok()
+    }
+}
+
+fn parse_byte(operand: &str) -> Result<u8, AsmError> {
+    parse_number(operand)
+        .filter(|v| *v <= 0xFF)
+        .map(|v| v as u8)
+        .ok_or_else(|| AsmError::BadOperand(operand.to_string()))
+}
+
+fn resolve_addr(operand: &str, labels: &HashMap<String, u16>) -> Result<u16, AsmError> {
+    if let Some(n) = parse_number(operand) {
+        return Ok(n & 0x0FFF);
+    }
+    labels
+        .get(operand.trim())
+        .copied()
+        .ok_or_else(|| AsmError::UndefinedLabel(operand.trim().to_string()))
+}
+
+fn expect_operands(mnemonic: &str, operands: &[String], expected: usize) -> Result<(), AsmError> {
+    if operands.len() != expected {
+        return Err(AsmError::WrongOperandCount {
+            mnemonic: mnemonic.to_string(),
+            expected,
+            found: operands.len(),
+        });
+    }
+    Ok(())
+}
+
+fn encode(mnemonic: &str, operands: &[String], labels: &HashMap<String, u16>) -> Result<u16, AsmError> {
+    let upper = mnemonic.to_ascii_uppercase();
+    match upper.as_str() {
+        "CLS" => {
+            expect_operands(mnemonic, operands, 0)?;
+            Ok(0x00E0)
+        }
+        "RET" => {
+            expect_operands(mnemonic, operands, 0)?;
+            Ok(0x00EE)
+        }
+        "JP" => {
+            if operands.len() == 2 {
+                // JP V0, addr
+                if !operands[0].eq_ignore_ascii_case("V0") {
+                    return Err(AsmError::BadOperand(operands[0].clone()));
+                }
+                let nnn = resolve_addr(&operands[1], labels)?;
+                Ok(0xB000 | nnn)
+            } else {
+                expect_operands(mnemonic, operands, 1)?;
+                let nnn = resolve_addr(&operands[0], labels)?;
+                Ok(0x1000 | nnn)
+            }
+        }
+        "CALL" => {
+            expect_operands(mnemonic, operands, 1)?;
+            let nnn = resolve_addr(&operands[0], labels)?;
+            Ok(0x2000 | nnn)
+        }
+        "SE" => {
+            expect_operands(mnemonic, operands, 2)?;
+            let x = parse_register(&operands[0])?;
+            if let Ok(y) = parse_register(&operands[1]) {
+                Ok(0x5000 | (x << 8) | (y << 4))
+            } else {
+                let kk = parse_byte(&operands[1])? as u16;
+                Ok(0x3000 | (x << 8) | kk)
+            }
+        }
+        "SNE" => {
+            expect_operands(mnemonic, operands, 2)?;
+            let x = parse_register(&operands[0])?;
+            if let Ok(y) = parse_register(&operands[1]) {
+                Ok(0x9000 | (x << 8) | (y << 4))
+            } else {
+                let kk = parse_byte(&operands[1])? as u16;
+                Ok(0x4000 | (x << 8) | kk)
+            }
+        }
+        "LD" => encode_ld(operands, labels),
+        "ADD" => {
+            expect_operands(mnemonic, operands, 2)?;
+            if operands[0].eq_ignore_ascii_case("I") {
+                let x = parse_register(&operands[1])?;
+                Ok(0xF01E | (x << 8))
+            } else {
+                let x = parse_register(&operands[0])?;
+                if let Ok(y) = parse_register(&operands[1]) {
+                    Ok(0x8004 | (x << 8) | (y << 4))
+                } else {
+                    let kk = parse_byte(&operands[1])? as u16;
+                    Ok(0x7000 | (x << 8) | kk)
+                }
+            }
+        }
+        "OR" => encode_logic(mnemonic, operands, 0x1),
+        "AND" => encode_logic(mnemonic, operands, 0x2),
+        "XOR" => encode_logic(mnemonic, operands, 0x3),
+        "SUB" => encode_logic(mnemonic, operands, 0x5),
+        "SUBN" => encode_logic(mnemonic, operands, 0x7),
+        "SHR" => {
+            expect_operands(mnemonic, operands, 1)?;
+            let x = parse_register(&operands[0])?;
+            Ok(0x8006 | (x << 8))
+        }
+        "SHL" => {
+            expect_operands(mnemonic, operands, 1)?;
+            let x = parse_register(&operands[0])?;
+            Ok(0x800E | (x << 8))
+        }
+        "RND" => {
+            expect_operands(mnemonic, operands, 2)?;
+            let x = parse_register(&operands[0])?;
+            let kk = parse_byte(&operands[1])? as u16;
+            Ok(0xC000 | (x << 8) | kk)
+        }
+        "DRW" => {
+            expect_operands(mnemonic, operands, 3)?;
+            let x = parse_register(&operands[0])?;
+            let y = parse_register(&operands[1])?;
+            let n = parse_number(&operands[2])
+                .filter(|v| *v <= 0xF)
+                .ok_or_else(|| AsmError::BadOperand(operands[2].clone()))?;
+            Ok(0xD000 | (x << 8) | (y << 4) | n)
+        }
+        "SKP" => {
+            expect_operands(mnemonic, operands, 1)?;
+            let x = parse_register(&operands[0])?;
+            Ok(0xE09E | (x << 8))
+        }
+        "SKNP" => {
+            expect_operands(mnemonic, operands, 1)?;
+            let x = parse_register(&operands[0])?;
+            Ok(0xE0A1 | (x << 8))
+        }
+        _ => Err(AsmError::UnknownMnemonic(mnemonic.to_string())),
+    }
+}
+
+fn encode_logic(mnemonic: &str, operands: &[String], low_nibble: u16) -> Result<u16, AsmError> {
+    expect_operands(mnemonic, operands, 2)?;
+    let x = parse_register(&operands[0])?;
+    let y = parse_register(&operands[1])?;
+    Ok(0x8000 | (x << 8) | (y << 4) | low_nibble)
+}
+
+fn encode_ld(operands: &[String], labels: &HashMap<String, u16>) -> Result<u16, AsmError> {
+    expect_operands("LD", operands, 2)?;
+    let dst = operands[0].trim();
+    let src = operands[1].trim();
+
+    if dst.eq_ignore_ascii_case("I") {
+        let nnn = resolve_addr(src, labels)?;
+        return Ok(0xA000 | nnn);
+    }
+    if dst.eq_ignore_ascii_case("DT") {
+        let x = parse_register(src)?;
+        return Ok(0xF015 | (x << 8));
+    }
+    if dst.eq_ignore_ascii_case("ST") {
+        let x = parse_register(src)?;
+        return Ok(0xF018 | (x << 8));
+    }
+    if dst.eq_ignore_ascii_case("F") {
+        let x = parse_register(src)?;
+        return Ok(0xF029 | (x << 8));
+    }
+    if dst.eq_ignore_ascii_case("B") {
+        let x = parse_register(src)?;
+        return Ok(0xF033 | (x << 8));
+    }
+    if dst.eq_ignore_ascii_case("[I]") {
+        let x = parse_register(src)?;
+        return Ok(0xF055 | (x << 8));
+    }
+
+    // Remaining forms all start with "LD Vx, ..."
+    let x = parse_register(dst)?;
+    if src.eq_ignore_ascii_case("DT") {
+        return Ok(0xF007 | (x << 8));
+    }
+    if src.eq_ignore_ascii_case("K") {
+        return Ok(0xF00A | (x << 8));
+    }
+    if src.eq_ignore_ascii_case("[I]") {
+        return Ok(0xF065 | (x << 8));
+    }
+    if let Ok(y) = parse_register(src) {
+        return Ok(0x8000 | (x << 8) | (y << 4));
+    }
+    let kk = parse_byte(src)? as u16;
+    Ok(0x6000 | (x << 8) | kk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Chip8;
+
+    #[test]
+    fn assembles_and_runs_a_loop_drawing_a_digit() {
+        let source = "
+            start:
+                LD I, 0        ; point I at the '0' font sprite
+                LD V0, 0
+                LD V1, 0
+                DRW V0, V1, 5
+                JP start
+        ";
+        let rom = assemble(source).expect("assembly should succeed");
+
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.tick(); // LD I, 0
+        chip8.tick(); // LD V0, 0
+        chip8.tick(); // LD V1, 0
+        chip8.tick(); // DRW V0, V1, 5
+
+        // The '0' glyph is 0xF0, 0x90, 0x90, 0x90, 0xF0 - top row is 4 lit pixels.
+        let display = chip8.get_display();
+        assert!(display[0] && display[1] && display[2] && display[3]);
+        assert!(!display[4]);
+    }
+
+    #[test]
+    fn rejects_undefined_labels() {
+        let err = assemble("JP nowhere").unwrap_err();
+        assert_eq!(err, AsmError::UndefinedLabel("nowhere".to_string()));
+    }
+
+    #[test]
+    fn db_directive_emits_raw_bytes() {
+        let rom = assemble("DB 0x01, 0x02, 3").unwrap();
+        assert_eq!(rom, vec![0x01, 0x02, 0x03]);
+    }
+}
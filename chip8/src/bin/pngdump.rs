@@ -0,0 +1,108 @@
+//! Headless PNG-sequence dumper: runs a ROM for a fixed number of frames and
+//! writes each one out as `frame_0001.png`, `frame_0002.png`, etc. No window is
+//! opened, so this doubles as a fixture generator for automated visual
+//! regression tests.
+
+use chip8::{Chip8, SCREEN_HEIGHT, SCREEN_WIDTH};
+use image::{GrayImage, Luma};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+const INSTRUCTION_PER_FRAME: u32 = 10;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        panic!("usage: pngdump <rom-path> --frames N --out dir");
+    }
+
+    let rom_path = &args[1];
+    let frames = parse_frames_arg(&args).unwrap_or(1);
+    let out_dir = parse_out_arg(&args).expect("missing required --out dir");
+
+    let rom = fs::read(rom_path).expect("Unable to open file");
+    let mut chip8 = Chip8::new();
+    chip8.load(&rom).expect("ROM file is empty");
+
+    fs::create_dir_all(&out_dir).expect("Unable to create output directory");
+    for frame in 1..=frames {
+        for _ in 0..INSTRUCTION_PER_FRAME {
+            chip8.tick();
+        }
+        chip8.tick_timers();
+
+        let path = out_dir.join(format!("frame_{frame:04}.png"));
+        display_to_png(&chip8).save(path).expect("Unable to write PNG frame");
+    }
+}
+
+/// Render plane 0 of the display as a monochrome PNG-ready image.
+fn display_to_png(chip: &Chip8) -> GrayImage {
+    let display = chip.get_display();
+    GrayImage::from_fn(SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32, |x, y| {
+        let lit = display[x as usize + SCREEN_WIDTH * y as usize];
+        Luma([if lit { 255 } else { 0 }])
+    })
+}
+
+/// Read a `--frames N` argument from the command line.
+fn parse_frames_arg(args: &[String]) -> Option<u32> {
+    args.iter().position(|arg| arg == "--frames").and_then(|i| args.get(i + 1)).and_then(|n| n.parse().ok())
+}
+
+/// Read a `--out dir` argument from the command line.
+fn parse_out_arg(args: &[String]) -> Option<PathBuf> {
+    args.iter().position(|arg| arg == "--out").and_then(|i| args.get(i + 1)).map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_frames_arg_reads_the_flag_and_defaults_to_none() {
+        let with_flag: Vec<String> =
+            ["pngdump", "rom.ch8", "--frames", "3"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_frames_arg(&with_flag), Some(3));
+
+        let without_flag: Vec<String> = ["pngdump", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_frames_arg(&without_flag), None);
+    }
+
+    #[test]
+    fn parse_out_arg_reads_the_flag_and_defaults_to_none() {
+        let with_flag: Vec<String> =
+            ["pngdump", "rom.ch8", "--out", "/tmp/frames"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_out_arg(&with_flag), Some(PathBuf::from("/tmp/frames")));
+
+        let without_flag: Vec<String> = ["pngdump", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_out_arg(&without_flag), None);
+    }
+
+    #[test]
+    fn running_three_frames_writes_three_png_files() {
+        let dir = std::env::temp_dir().join(format!("chip8_pngdump_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        // LD I, 0; LD V0, 0; LD V1, 0; DRW V0, V1, 5; JP self
+        let rom = [0xA0, 0x00, 0x60, 0x00, 0x61, 0x00, 0xD0, 0x15, 0x12, 0x08];
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        fs::create_dir_all(&dir).unwrap();
+        for frame in 1..=3 {
+            for _ in 0..INSTRUCTION_PER_FRAME {
+                chip8.tick();
+            }
+            chip8.tick_timers();
+            display_to_png(&chip8).save(dir.join(format!("frame_{frame:04}.png"))).unwrap();
+        }
+
+        for frame in 1..=3 {
+            assert!(dir.join(format!("frame_{frame:04}.png")).is_file());
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
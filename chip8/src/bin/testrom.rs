@@ -0,0 +1,84 @@
+//! Headless test-ROM runner: loads a ROM, runs it for a fixed number of frames,
+//! then compares [`Chip8::display_hash`] against a table of known-good hashes
+//! keyed by filename, printing PASS/FAIL for CI-friendly compatibility checking.
+
+use chip8::Chip8;
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const FRAMES: u32 = 5;
+const INSTRUCTION_PER_FRAME: u32 = 10;
+
+/// Known-good [`Chip8::display_hash`] results after running a ROM for [`FRAMES`]
+/// frames, keyed by the ROM's file name.
+const EXPECTED_HASHES: &[(&str, u64)] = &[("digit0.ch8", DIGIT0_ROM_HASH)];
+
+/// A tiny bundled ROM (`LD I, 0; LD V0, 0; LD V1, 0; DRW V0, V1, 5; JP self`) that
+/// draws the built-in "0" font glyph in the top-left corner, then loops forever.
+#[cfg(test)]
+const DIGIT0_ROM: [u8; 10] = [0xA0, 0x00, 0x60, 0x00, 0x61, 0x00, 0xD0, 0x15, 0x12, 0x08];
+const DIGIT0_ROM_HASH: u64 = 17006537283474199402;
+
+/// Run `rom` headlessly for `frames` frames and return its final display hash.
+fn run_headless(rom: &[u8], frames: u32) -> u64 {
+    let mut chip8 = Chip8::new();
+    chip8.load(rom).expect("ROM file is empty");
+    for _ in 0..frames {
+        for _ in 0..INSTRUCTION_PER_FRAME {
+            chip8.tick();
+        }
+        chip8.tick_timers();
+    }
+    chip8.display_hash()
+}
+
+/// Look up the known-good hash for a ROM by its file name.
+fn expected_hash_for(filename: &str) -> Option<u64> {
+    EXPECTED_HASHES
+        .iter()
+        .find(|&&(name, _)| name == filename)
+        .map(|&(_, hash)| hash)
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        panic!("usage: testrom <rom-path>");
+    }
+
+    let rom_path = Path::new(&args[1]);
+    let filename = rom_path.file_name().and_then(|f| f.to_str()).unwrap_or(&args[1]);
+    let rom = fs::read(rom_path).expect("Unable to open file");
+    let actual_hash = run_headless(&rom, FRAMES);
+
+    match expected_hash_for(filename) {
+        Some(expected_hash) if expected_hash == actual_hash => {
+            println!("PASS {filename}");
+        }
+        Some(expected_hash) => {
+            println!("FAIL {filename} (expected {expected_hash:#x}, got {actual_hash:#x})");
+            std::process::exit(1);
+        }
+        None => {
+            println!("FAIL {filename} (no expected hash registered)");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit0_rom_matches_its_bundled_expected_hash() {
+        let hash = run_headless(&DIGIT0_ROM, FRAMES);
+        assert_eq!(Some(hash), expected_hash_for("digit0.ch8"));
+    }
+
+    #[test]
+    fn unregistered_filenames_have_no_expected_hash() {
+        assert_eq!(expected_hash_for("unknown.ch8"), None);
+    }
+}
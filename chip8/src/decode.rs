@@ -0,0 +1,126 @@
+//! A structured decoder that turns raw CHIP-8 opcodes into an [`Instruction`],
+//! for tooling that wants operands as typed fields instead of a mnemonic string.
+//! Mirrors the opcode coverage of [`crate::disasm`].
+
+/// A decoded CHIP-8 instruction with its operands broken out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Nop,
+    Cls,
+    Ret,
+    Jp { addr: u16 },
+    JpV0 { addr: u16 },
+    Call { addr: u16 },
+    Se { x: u8, kk: u8 },
+    SeReg { x: u8, y: u8 },
+    Sne { x: u8, kk: u8 },
+    SneReg { x: u8, y: u8 },
+    LdVxByte { x: u8, kk: u8 },
+    LdVxVy { x: u8, y: u8 },
+    Add { x: u8, kk: u8 },
+    AddReg { x: u8, y: u8 },
+    Or { x: u8, y: u8 },
+    And { x: u8, y: u8 },
+    Xor { x: u8, y: u8 },
+    Sub { x: u8, y: u8 },
+    Shr { x: u8 },
+    Subn { x: u8, y: u8 },
+    Shl { x: u8 },
+    LdI(u16),
+    Rnd { x: u8, kk: u8 },
+    Drw { x: u8, y: u8, n: u8 },
+    Skp { x: u8 },
+    Sknp { x: u8 },
+    LdVxDt { x: u8 },
+    LdVxK { x: u8 },
+    LdDtVx { x: u8 },
+    LdStVx { x: u8 },
+    AddIVx { x: u8 },
+    LdFVx { x: u8 },
+    LdBVx { x: u8 },
+    LdIVx { x: u8 },
+    LdVxI { x: u8 },
+    /// An opcode that doesn't decode to any known instruction.
+    Unknown(u16),
+}
+
+/// Decode a single big-endian opcode into a structured [`Instruction`].
+pub fn decode(opcode: u16) -> Instruction {
+    let digit1 = (opcode & 0xF000) >> 12;
+    let digit2 = ((opcode & 0x0F00) >> 8) as u8;
+    let digit3 = ((opcode & 0x00F0) >> 4) as u8;
+    let digit4 = (opcode & 0x000F) as u8;
+    let nnn = opcode & 0x0FFF;
+    let kk = (opcode & 0x00FF) as u8;
+
+    match (digit1, digit2, digit3, digit4) {
+        (0x0, 0x0, 0x0, 0x0) => Instruction::Nop,
+        (0x0, 0x0, 0xE, 0x0) => Instruction::Cls,
+        (0x0, 0x0, 0xE, 0xE) => Instruction::Ret,
+        (0x1, _, _, _) => Instruction::Jp { addr: nnn },
+        (0x2, _, _, _) => Instruction::Call { addr: nnn },
+        (0x3, x, _, _) => Instruction::Se { x, kk },
+        (0x4, x, _, _) => Instruction::Sne { x, kk },
+        (0x5, x, y, 0x0) => Instruction::SeReg { x, y },
+        (0x6, x, _, _) => Instruction::LdVxByte { x, kk },
+        (0x7, x, _, _) => Instruction::Add { x, kk },
+        (0x8, x, y, 0x0) => Instruction::LdVxVy { x, y },
+        (0x8, x, y, 0x1) => Instruction::Or { x, y },
+        (0x8, x, y, 0x2) => Instruction::And { x, y },
+        (0x8, x, y, 0x3) => Instruction::Xor { x, y },
+        (0x8, x, y, 0x4) => Instruction::AddReg { x, y },
+        (0x8, x, y, 0x5) => Instruction::Sub { x, y },
+        (0x8, x, _, 0x6) => Instruction::Shr { x },
+        (0x8, x, y, 0x7) => Instruction::Subn { x, y },
+        (0x8, x, _, 0xE) => Instruction::Shl { x },
+        (0x9, x, y, 0x0) => Instruction::SneReg { x, y },
+        (0xA, _, _, _) => Instruction::LdI(nnn),
+        (0xB, _, _, _) => Instruction::JpV0 { addr: nnn },
+        (0xC, x, _, _) => Instruction::Rnd { x, kk },
+        (0xD, x, y, n) => Instruction::Drw { x, y, n },
+        (0xE, x, 0x9, 0xE) => Instruction::Skp { x },
+        (0xE, x, 0xA, 0x1) => Instruction::Sknp { x },
+        (0xF, x, 0x0, 0x7) => Instruction::LdVxDt { x },
+        (0xF, x, 0x0, 0xA) => Instruction::LdVxK { x },
+        (0xF, x, 0x1, 0x5) => Instruction::LdDtVx { x },
+        (0xF, x, 0x1, 0x8) => Instruction::LdStVx { x },
+        (0xF, x, 0x1, 0xE) => Instruction::AddIVx { x },
+        (0xF, x, 0x2, 0x9) => Instruction::LdFVx { x },
+        (0xF, x, 0x3, 0x3) => Instruction::LdBVx { x },
+        (0xF, x, 0x5, 0x5) => Instruction::LdIVx { x },
+        (0xF, x, 0x6, 0x5) => Instruction::LdVxI { x },
+        _ => Instruction::Unknown(opcode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_control_flow_and_immediate_instructions() {
+        assert_eq!(decode(0x00E0), Instruction::Cls);
+        assert_eq!(decode(0x00EE), Instruction::Ret);
+        assert_eq!(decode(0x1234), Instruction::Jp { addr: 0x234 });
+        assert_eq!(decode(0x2345), Instruction::Call { addr: 0x345 });
+        assert_eq!(decode(0x63AB), Instruction::LdVxByte { x: 3, kk: 0xAB });
+        assert_eq!(decode(0xA123), Instruction::LdI(0x123));
+    }
+
+    #[test]
+    fn decodes_the_draw_instruction_with_all_three_operands() {
+        assert_eq!(decode(0xD125), Instruction::Drw { x: 1, y: 2, n: 5 });
+    }
+
+    #[test]
+    fn decodes_arithmetic_and_f_family_instructions() {
+        assert_eq!(decode(0x8014), Instruction::AddReg { x: 0, y: 1 });
+        assert_eq!(decode(0xF033), Instruction::LdBVx { x: 0 });
+        assert_eq!(decode(0xF165), Instruction::LdVxI { x: 1 });
+    }
+
+    #[test]
+    fn falls_back_to_unknown_for_unrecognized_opcodes() {
+        assert_eq!(decode(0x5001), Instruction::Unknown(0x5001));
+    }
+}
@@ -0,0 +1,168 @@
+//! A disassembler that turns raw CHIP-8 opcodes back into mnemonics, mirroring
+//! the encoding in [`crate::asm`]. Jump/call targets are printed as absolute
+//! addresses since a plain ROM carries no label information.
+
+/// Disassemble a single big-endian opcode into a mnemonic line.
+pub fn disassemble_instruction(opcode: u16) -> String {
+    let digit1 = (opcode & 0xF000) >> 12;
+    let digit2 = (opcode & 0x0F00) >> 8;
+    let digit3 = (opcode & 0x00F0) >> 4;
+    let digit4 = opcode & 0x000F;
+    let nnn = opcode & 0x0FFF;
+    let kk = opcode & 0x00FF;
+
+    match (digit1, digit2, digit3, digit4) {
+        (0x0, 0x0, 0x0, 0x0) => "NOP".to_string(),
+        (0x0, 0x0, 0xE, 0x0) => "CLS".to_string(),
+        (0x0, 0x0, 0xE, 0xE) => "RET".to_string(),
+        (0x1, _, _, _) => format!("JP {nnn:#05x}"),
+        (0x2, _, _, _) => format!("CALL {nnn:#05x}"),
+        (0x3, x, _, _) => format!("SE V{x:X}, {kk:#04x}"),
+        (0x4, x, _, _) => format!("SNE V{x:X}, {kk:#04x}"),
+        (0x5, x, y, 0x0) => format!("SE V{x:X}, V{y:X}"),
+        (0x6, x, _, _) => format!("LD V{x:X}, {kk:#04x}"),
+        (0x7, x, _, _) => format!("ADD V{x:X}, {kk:#04x}"),
+        (0x8, x, y, 0x0) => format!("LD V{x:X}, V{y:X}"),
+        (0x8, x, y, 0x1) => format!("OR V{x:X}, V{y:X}"),
+        (0x8, x, y, 0x2) => format!("AND V{x:X}, V{y:X}"),
+        (0x8, x, y, 0x3) => format!("XOR V{x:X}, V{y:X}"),
+        (0x8, x, y, 0x4) => format!("ADD V{x:X}, V{y:X}"),
+        (0x8, x, y, 0x5) => format!("SUB V{x:X}, V{y:X}"),
+        (0x8, x, _, 0x6) => format!("SHR V{x:X}"),
+        (0x8, x, y, 0x7) => format!("SUBN V{x:X}, V{y:X}"),
+        (0x8, x, _, 0xE) => format!("SHL V{x:X}"),
+        (0x9, x, y, 0x0) => format!("SNE V{x:X}, V{y:X}"),
+        (0xA, _, _, _) => format!("LD I, {nnn:#05x}"),
+        (0xB, _, _, _) => format!("JP V0, {nnn:#05x}"),
+        (0xC, x, _, _) => format!("RND V{x:X}, {kk:#04x}"),
+        (0xD, x, y, n) => format!("DRW V{x:X}, V{y:X}, {n:#03x}"),
+        (0xE, x, 0x9, 0xE) => format!("SKP V{x:X}"),
+        (0xE, x, 0xA, 0x1) => format!("SKNP V{x:X}"),
+        (0xF, x, 0x0, 0x7) => format!("LD V{x:X}, DT"),
+        (0xF, x, 0x0, 0xA) => format!("LD V{x:X}, K"),
+        (0xF, x, 0x1, 0x5) => format!("LD DT, V{x:X}"),
+        (0xF, x, 0x1, 0x8) => format!("LD ST, V{x:X}"),
+        (0xF, x, 0x1, 0xE) => format!("ADD I, V{x:X}"),
+        (0xF, x, 0x2, 0x9) => format!("LD F, V{x:X}"),
+        (0xF, x, 0x3, 0x3) => format!("LD B, V{x:X}"),
+        (0xF, x, 0x5, 0x5) => format!("LD [I], V{x:X}"),
+        (0xF, x, 0x6, 0x5) => format!("LD V{x:X}, [I]"),
+        _ => format!("DW {opcode:#06x}"),
+    }
+}
+
+/// Whether `opcode` decodes to a known instruction rather than falling back to `DW`.
+pub(crate) fn is_known_opcode(opcode: u16) -> bool {
+    let digit1 = (opcode & 0xF000) >> 12;
+    let digit2 = (opcode & 0x0F00) >> 8;
+    let digit3 = (opcode & 0x00F0) >> 4;
+    let digit4 = opcode & 0x000F;
+
+    // NOP/CLS/RET are only `0000`/`00E0`/`00EE`; any other `0x0___` is `DW`.
+    if digit1 == 0x0 && digit2 != 0x0 {
+        return false;
+    }
+
+    matches!(
+        (digit1, digit3, digit4),
+        (0x0, 0x0, 0x0)
+            | (0x0, 0xE, 0x0)
+            | (0x0, 0xE, 0xE)
+            | (0x1, _, _)
+            | (0x2, _, _)
+            | (0x3, _, _)
+            | (0x4, _, _)
+            | (0x6, _, _)
+            | (0x7, _, _)
+            | (0xA, _, _)
+            | (0xB, _, _)
+            | (0xC, _, _)
+            | (0xD, _, _)
+            | (0x5, _, 0x0)
+            | (0x9, _, 0x0)
+            | (0x8, _, 0x0..=0x7)
+            | (0x8, _, 0xE)
+            | (0xE, 0x9, 0xE)
+            | (0xE, 0xA, 0x1)
+            | (0xF, 0x0, 0x7)
+            | (0xF, 0x0, 0xA)
+            | (0xF, 0x1, 0x5)
+            | (0xF, 0x1, 0x8)
+            | (0xF, 0x1, 0xE)
+            | (0xF, 0x2, 0x9)
+            | (0xF, 0x3, 0x3)
+            | (0xF, 0x5, 0x5)
+            | (0xF, 0x6, 0x5)
+    )
+}
+
+/// Disassemble a whole ROM, two bytes at a time, into one mnemonic line per instruction.
+///
+/// A trailing odd byte (if `rom.len()` is not a multiple of two) is ignored.
+pub fn disassemble(rom: &[u8]) -> Vec<String> {
+    rom.chunks_exact(2)
+        .map(|pair| disassemble_instruction(u16::from_be_bytes([pair[0], pair[1]])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    fn mnemonic(line: &str) -> &str {
+        line.split_whitespace().next().unwrap_or("")
+    }
+
+    #[test]
+    fn round_trips_arithmetic_draw_and_f_family() {
+        let source = "
+            LD V0, 1
+            LD V1, 2
+            OR V0, V1
+            AND V0, V1
+            XOR V0, V1
+            ADD V0, V1
+            SUB V0, V1
+            SUBN V0, V1
+            SHR V0
+            SHL V0
+            LD I, 0x300
+            DRW V0, V1, 5
+            LD V0, DT
+            LD V0, K
+            LD DT, V0
+            LD ST, V0
+            ADD I, V0
+            LD F, V0
+            LD B, V0
+            LD [I], V0
+            LD V0, [I]
+        ";
+        let rom = assemble(source).unwrap();
+        let disassembled = disassemble(&rom);
+
+        let expected_mnemonics = [
+            "LD", "LD", "OR", "AND", "XOR", "ADD", "SUB", "SUBN", "SHR", "SHL", "LD", "DRW", "LD",
+            "LD", "LD", "LD", "ADD", "LD", "LD", "LD", "LD",
+        ];
+        let actual_mnemonics: Vec<&str> = disassembled.iter().map(|l| mnemonic(l)).collect();
+        assert_eq!(actual_mnemonics, expected_mnemonics);
+    }
+
+    #[test]
+    fn round_trips_jumps_modulo_label_resolution() {
+        let source = "
+            start:
+                JP loop
+            loop:
+                CALL start
+        ";
+        let rom = assemble(source).unwrap();
+        let disassembled = disassemble(&rom);
+        assert_eq!(mnemonic(&disassembled[0]), "JP");
+        assert_eq!(disassembled[0], "JP 0x202");
+        assert_eq!(mnemonic(&disassembled[1]), "CALL");
+        assert_eq!(disassembled[1], "CALL 0x200");
+    }
+}
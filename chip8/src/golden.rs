@@ -0,0 +1,73 @@
+//! On-disk format for golden display frames, so a regression test suite can
+//! store expected frames (see [`crate::Chip8::packed_display`]) as
+//! self-describing fixture files instead of bare 256-byte blobs.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Magic bytes identifying a golden frame file.
+const GOLDEN_FRAME_MAGIC: &[u8; 4] = b"C8GF";
+/// Bump alongside a layout change and branch on it in [`read_frame`] if old
+/// fixtures need to keep loading.
+const GOLDEN_FRAME_VERSION: u8 = 1;
+
+/// Write `packed` (a golden frame, see [`crate::Chip8::packed_display`]) to
+/// `path`, prefixed with a tiny header (magic + version) so a fixture file is
+/// self-describing rather than an anonymous blob of bytes.
+pub fn write_frame<P: AsRef<Path>>(path: P, packed: &[u8]) -> io::Result<()> {
+    let mut out = Vec::with_capacity(GOLDEN_FRAME_MAGIC.len() + 1 + packed.len());
+    out.extend_from_slice(GOLDEN_FRAME_MAGIC);
+    out.push(GOLDEN_FRAME_VERSION);
+    out.extend_from_slice(packed);
+    fs::write(path, out)
+}
+
+/// Read a golden frame previously written by [`write_frame`], returning the
+/// packed display bytes with the header stripped. Fails with
+/// `io::ErrorKind::InvalidData` if `path` doesn't hold a golden frame this
+/// build understands (bad magic, unknown version, or truncated data).
+pub fn read_frame<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    let bytes = fs::read(path)?;
+    let header_len = GOLDEN_FRAME_MAGIC.len() + 1;
+    let header = bytes
+        .get(..header_len)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated golden frame"))?;
+    if &header[..GOLDEN_FRAME_MAGIC.len()] != GOLDEN_FRAME_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a chip8 golden frame"));
+    }
+    if header[GOLDEN_FRAME_MAGIC.len()] != GOLDEN_FRAME_VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported golden frame version"));
+    }
+    Ok(bytes[header_len..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_frame_and_read_frame_round_trip_a_packed_display() {
+        let path = std::env::temp_dir()
+            .join(format!("chip8_golden_frame_test_{}", std::process::id()));
+        let packed = [0xABu8; 256];
+
+        write_frame(&path, &packed).unwrap();
+        let read_back = read_frame(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_back, packed);
+    }
+
+    #[test]
+    fn read_frame_rejects_a_file_that_isnt_a_golden_frame() {
+        let path = std::env::temp_dir()
+            .join(format!("chip8_golden_frame_bad_test_{}", std::process::id()));
+        std::fs::write(&path, b"not a golden frame").unwrap();
+
+        let err = read_frame(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,53 @@
+//! The de facto standard QWERTY layout for the CHIP-8 hex keypad, shared by
+//! every frontend so it can be unit tested without pulling in SDL or a terminal.
+//!
+//! ```text
+//! 1 2 3 4        1 2 3 C
+//! q w e r   ->   4 5 6 D
+//! a s d f        7 8 9 E
+//! z x c v        A 0 B F
+//! ```
+
+/// Map a QWERTY key character to its CHIP-8 keypad index (0x0-0xF), or `None`
+/// if the character isn't one of the 16 mapped keys. Case-insensitive.
+pub fn qwerty_to_key(c: char) -> Option<usize> {
+    match c.to_ascii_lowercase() {
+        '1' => Some(0x1),
+        '2' => Some(0x2),
+        '3' => Some(0x3),
+        '4' => Some(0xC),
+        'q' => Some(0x4),
+        'w' => Some(0x5),
+        'e' => Some(0x6),
+        'r' => Some(0xD),
+        'a' => Some(0x7),
+        's' => Some(0x8),
+        'd' => Some(0x9),
+        'f' => Some(0xE),
+        'z' => Some(0xA),
+        'x' => Some(0x0),
+        'c' => Some(0xB),
+        'v' => Some(0xF),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qwerty_to_key_maps_known_keys_to_the_hex_keypad() {
+        assert_eq!(qwerty_to_key('1'), Some(0x1));
+        assert_eq!(qwerty_to_key('q'), Some(0x4));
+        assert_eq!(qwerty_to_key('x'), Some(0x0));
+        assert_eq!(qwerty_to_key('v'), Some(0xF));
+    }
+
+    #[test]
+    fn qwerty_to_key_is_case_insensitive_and_rejects_unmapped_keys() {
+        assert_eq!(qwerty_to_key('Q'), Some(0x4));
+        assert_eq!(qwerty_to_key('u'), None);
+        assert_eq!(qwerty_to_key(' '), None);
+    }
+}
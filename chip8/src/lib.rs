@@ -1,4 +1,33 @@
-use rand::random;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+pub mod asm;
+pub mod decode;
+pub mod disasm;
+pub mod golden;
+pub mod keymap;
+pub mod quirks;
+pub mod replay;
+pub mod rom;
+pub mod scheduler;
+pub mod snapshot;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use quirks::{
+    FlagWriteOrder, Fx0aKeyOrder, LoadStoreQuirk, MemoryModel, Quirks, QuirksPreset,
+    ShiftQuirk, StackOverflowBehavior,
+};
+pub use replay::Replay;
+pub use scheduler::{FrameScheduler, FrameStep};
+pub use snapshot::MachineSnapshot;
 
 const FONTSET_SIZE: usize = 80;
 
@@ -25,16 +54,204 @@ const FONTSET: [u8; FONTSET_SIZE] = [
 pub const SCREEN_WIDTH: usize = 64;
 pub const SCREEN_HEIGHT: usize = 32;
 
-const RAM_SIZE: usize = 4096;
+/// The display's size in SUPER-CHIP hi-res mode (`00FF`). See [`Chip8::display_width`]/
+/// [`Chip8::display_height`].
+pub const HI_RES_WIDTH: usize = 128;
+pub const HI_RES_HEIGHT: usize = 64;
+
 const V_REGISTERS: usize = 16;
 const STACK_SIZE: usize = 16;
 const NUM_KEYS: usize = 16;
 
+/// Errors from operations that access RAM or registers directly, bypassing opcode
+/// execution, or from opcode execution itself under a stricter quirk setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chip8Error {
+    OutOfBounds { addr: u16, len: usize },
+    /// An opcode matched a known instruction family but not one of its defined
+    /// forms (e.g. `5xy1`), raised only when [`Quirks::strict_undefined_forms`]
+    /// is set; otherwise these forms are silently treated as a no-op.
+    UnknownOpcode { opcode: u16 },
+    /// [`Chip8::load`] was given a zero-length ROM. Loading it anyway would leave
+    /// the program counter pointed at whatever was already in RAM (all zeros on a
+    /// fresh machine, which decodes as an endless run of no-op `0nnn`s).
+    EmptyRom,
+    /// [`Chip8::load`]'s target range would overlap the reserved font region at
+    /// the bottom of RAM (see [`Chip8::with_start_addr`]), clobbering the
+    /// built-in hex digit sprites `Fx29` relies on.
+    OverwritesFontRegion { start: u16, len: usize },
+    /// [`Chip8::set_display_packed`] was given a buffer that isn't exactly
+    /// `SCREEN_WIDTH * SCREEN_HEIGHT / 8` bytes long.
+    WrongPackedDisplayLen { expected: usize, actual: usize },
+    /// `CALL` was issued with the call stack already full (16 nested calls
+    /// deep), under [`Quirks::stack_overflow_behavior`]'s default `Error` setting.
+    StackOverflow,
+}
+
+impl fmt::Display for Chip8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Chip8Error::OutOfBounds { addr, len } => {
+                write!(f, "access of {len} byte(s) at {addr:#06x} is out of bounds")
+            }
+            Chip8Error::UnknownOpcode { opcode } => {
+                write!(f, "undefined opcode form: {opcode:#06x}")
+            }
+            Chip8Error::EmptyRom => write!(f, "cannot load an empty ROM"),
+            Chip8Error::OverwritesFontRegion { start, len } => {
+                write!(f, "load of {len} byte(s) at {start:#06x} would overwrite the font region")
+            }
+            Chip8Error::WrongPackedDisplayLen { expected, actual } => {
+                write!(f, "packed display must be {expected} byte(s), got {actual}")
+            }
+            Chip8Error::StackOverflow => write!(f, "call stack overflow"),
+        }
+    }
+}
+
+impl std::error::Error for Chip8Error {}
+
+/// One entry of [`Chip8::call_frames`]: a pending `CALL` on the stack, with both
+/// the address execution resumes at on `RET` and the address of the `CALL`
+/// itself, for a debugger that wants to show where each nested call came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallFrame {
+    /// Where `RET` will resume execution - the raw value [`Chip8::call_stack`] stores.
+    pub return_addr: u16,
+    /// Where the `CALL` that pushed `return_addr` lives, i.e. `return_addr - 2`.
+    pub call_site: u16,
+}
+
+/// What a [`MemoryRegion`] holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryRegionKind {
+    /// The built-in hex digit sprites, at the very start of RAM.
+    Font,
+    /// The loaded ROM, from [`START_ADDRESS`] (or a custom start address, see
+    /// [`Chip8::with_start_addr`]) to its length.
+    Program,
+    /// Unused RAM available to the running program - either before the
+    /// program area (if it doesn't start right after the font) or after it.
+    Free,
+}
+
+/// One entry of [`Chip8::memory_regions`]: a labeled, non-overlapping span of
+/// RAM, for a debugger's colored memory-map view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub kind: MemoryRegionKind,
+    /// Start address, inclusive.
+    pub start: usize,
+    /// End address, exclusive.
+    pub end: usize,
+}
+
+/// Outcome of [`Chip8::run_to`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RunToResult {
+    /// The program counter reached the target address.
+    Reached,
+    /// `max_cycles` elapsed before the target address was reached.
+    CycleLimitReached,
+    /// A tick raised a memory error before the target address was reached.
+    Error(Chip8Error),
+}
+
+/// Outcome of [`Chip8::tick`] (and [`Chip8::run_with_limit`], which adds
+/// [`Self::LimitReached`] on top).
+///
+/// Several of these can apply to the same tick at once - e.g. the instruction
+/// that ran was both a breakpoint and wrote a watched address. Precedence,
+/// highest first, is the order variants are listed here: [`Self::Halted`],
+/// then [`Self::Error`], then [`Self::Breakpoint`], then [`Self::Watchpoint`],
+/// then [`Self::WaitingForKey`], then [`Self::Normal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TickOutcome {
+    /// `max_cycles` ticks ran without hitting a memory error. Only returned by
+    /// [`Chip8::run_with_limit`], never by a single [`Chip8::tick`].
+    LimitReached,
+    /// The ROM executed SUPER-CHIP's `00FD` (EXIT), halting the interpreter.
+    /// Once this fires, further ticks are no-ops that keep returning it.
+    Halted,
+    /// Opcode execution raised an error (e.g. a RAM-bounds error or a
+    /// [`Chip8Error::StackOverflow`]); also recorded for
+    /// [`Chip8::take_memory_error`]/[`Chip8::last_error`].
+    Error(Chip8Error),
+    /// The instruction that just ran sits at an address added via
+    /// [`Chip8::add_breakpoint`].
+    Breakpoint(u16),
+    /// The instruction that just ran wrote to an address added via
+    /// [`Chip8::add_watchpoint`]. Like [`Chip8::take_self_modify_event`]'s
+    /// detection, only the base address of a multi-byte write (`Fx33`/`Fx55`/
+    /// XO-CHIP's `5xy2`) is checked, not every byte in its range.
+    Watchpoint(u16),
+    /// `Fx0A` re-fetched itself because no key was pressed yet.
+    WaitingForKey,
+    /// Nothing else applied - a plain, uneventful instruction.
+    Normal,
+}
+
+/// A pluggable source of key state, for headless testing or a netplay client
+/// that wants `Ex9E`/`ExA1`/`Fx0A` to consult something other than the state
+/// set via [`Chip8::keypress`]/[`Chip8::set_keys`].
+pub trait InputSource: fmt::Debug {
+    fn is_pressed(&self, key: usize) -> bool;
+}
+
+/// A pluggable source of random bytes for `Cxkk`, for a hardware RNG, a
+/// recorded sequence replayed in a test, or any other source besides
+/// [`Chip8::with_seed`]'s internal seeded generator.
+pub trait RandomSource: fmt::Debug {
+    fn next_byte(&mut self) -> u8;
+}
+
+/// One entry in [`Chip8::register_write_log`]: a V-register or the I-register
+/// changing from `old` to `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegisterWrite {
+    V { index: usize, old: u8, new: u8 },
+    I { old: u16, new: u16 },
+}
+
+/// One entry in [`Chip8::branch_log`]: a skip instruction's decision, for
+/// debugging why a ROM's control flow went the way it did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchDecision {
+    /// Address of the skip instruction itself.
+    pub pc: u16,
+    pub opcode: u16,
+    /// Whether the next instruction was actually skipped.
+    pub skipped: bool,
+}
+
+/// One entry in [`Chip8::timer_log`]: the delay/sound timers' values after a
+/// single [`Chip8::tick_timers`] call, for diagnosing a ROM that misuses them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerLogEntry {
+    /// The number of instructions executed so far, at the time of this call.
+    pub cycle: u64,
+    pub delay: u8,
+    pub sound: u8,
+}
+
+/// Outcome of drawing a sprite into a single bitplane, aggregated across planes
+/// by the `Dxyn` handler to compute the shared `VF` collision flag.
+struct SpriteDrawResult {
+    flipped: bool,
+    collision_count: u32,
+    collided_rows: u32,
+    clipped_rows: u32,
+}
+
 #[derive(Debug)]
 pub struct Chip8 {
     program_counter: u16,
-    ram: [u8; RAM_SIZE],
-    screen: [bool; SCREEN_WIDTH * SCREEN_HEIGHT],
+    ram: Vec<u8>,
+    /// Row-major, `display_width() * display_height()` long: [`SCREEN_WIDTH`] x
+    /// [`SCREEN_HEIGHT`] normally, [`HI_RES_WIDTH`] x [`HI_RES_HEIGHT`] after
+    /// `00FF`. Resized (and cleared) by [`Self::op_low_res`]/[`Self::op_hi_res`]
+    /// whenever the mode changes.
+    screen: Vec<bool>,
     v_registers: [u8; V_REGISTERS],
     i_register: u16,
     delay_timer_register: u8,
@@ -42,17 +259,254 @@ pub struct Chip8 {
     stack_pointer: u8,
     stack: [u16; STACK_SIZE],
     key_states: [bool; NUM_KEYS],
+    timer_accumulator: Duration,
+    last_collision_count: u32,
+    waiting_for_key: bool,
+    opcode_histogram: HashMap<u16, u64>,
+    quirks: Quirks,
+    cycle_count: u64,
+    recording_input: bool,
+    input_log: Vec<(u64, usize, bool)>,
+    vip_cycle_estimate: u64,
+    pending_memory_error: Option<Chip8Error>,
+    detect_self_modify: bool,
+    self_modify_event: Option<(u16, u16)>,
+    pixel_changes: u32,
+    start_addr: u16,
+    /// Same layout as [`Self::screen`], for the second XO-CHIP bitplane.
+    screen_plane2: Vec<bool>,
+    selected_planes: u8,
+    screen_dirty: bool,
+    /// Keys in the order they were last pressed, oldest first, for
+    /// [`Fx0aKeyOrder::MostRecent`]. Only updated by [`Self::keypress`].
+    key_press_order: Vec<usize>,
+    /// When set, `Ex9E`/`ExA1`/`Fx0A` consult this instead of `key_states`.
+    input_source: Option<Box<dyn InputSource>>,
+    /// CPU cycles run since the last timer decrement in [`Self::run_cycle_clocked`].
+    cycle_clock_accumulator: u64,
+    record_register_writes: bool,
+    register_write_log: Vec<(u64, RegisterWrite)>,
+    /// Rate [`Self::update`] decrements the delay/sound timers at, in Hz.
+    /// Standard CHIP-8 is 60Hz; see [`Self::set_timer_hz`].
+    timer_hz: u32,
+    /// Seed backing `rng`, kept around so [`Self::seed`] can report it (e.g. to
+    /// bundle into a [`Replay`]) and [`Self::save_replay_to_path`] doesn't need
+    /// to reach into the PRNG's internal state.
+    seed: u64,
+    /// Backs `Cxkk` (`op_rnd`). Seeded from `seed` so a run recorded with
+    /// [`Self::with_seed`] and replayed with the same seed reproduces identical
+    /// random draws.
+    rng: StdRng,
+    /// Set by SUPER-CHIP's `00FD` (EXIT). Once true, [`Self::tick`] is a no-op;
+    /// a frontend should check [`Self::is_halted`] and stop or show an "exited"
+    /// message instead of continuing to call it.
+    halted: bool,
+    /// Set by `00E0` (CLS), consumed by [`Self::take_cls_event`].
+    cls_event: bool,
+    /// Routes `Cxkk` through an injected [`RandomSource`] instead of `rng`, if set.
+    random_source: Option<Box<dyn RandomSource>>,
+    /// The sound timer's value when the currently-sounding beep started, set by
+    /// [`Self::op_ld_st_vx`] and consumed by [`Self::tick_timers`] once it ends.
+    beep_start_value: Option<u8>,
+    /// How many frames the most recently completed beep lasted, for audio-timing
+    /// tests. `None` until a beep has actually finished at least once.
+    last_beep_frames: Option<u32>,
+    /// Length of the ROM passed to [`Self::load`], for [`Self::memory_regions`].
+    rom_len: usize,
+    record_branch_decisions: bool,
+    branch_log: Vec<BranchDecision>,
+    /// Addresses that make [`Self::tick`] report [`TickOutcome::Breakpoint`]
+    /// when the instruction there runs. Set via [`Self::add_breakpoint`].
+    breakpoints: Vec<u16>,
+    /// Addresses that make [`Self::tick`] report [`TickOutcome::Watchpoint`]
+    /// when written to. Set via [`Self::add_watchpoint`].
+    watchpoints: Vec<u16>,
+    /// Set by [`Self::check_watchpoint`] during opcode execution, consumed by
+    /// [`Self::tick`] once it decides that tick's [`TickOutcome`].
+    watchpoint_hit: Option<u16>,
+    /// Set by SUPER-CHIP's `00FF` (HIGH)/`00FE` (LOW). See [`Self::is_hi_res`].
+    hi_res: bool,
+    record_timer_log: bool,
+    timer_log: Vec<TimerLogEntry>,
+    /// Whether [`Self::tick`] checks for a jump/call landing outside the loaded
+    /// ROM. See [`Self::detect_out_of_bounds_jump`].
+    detect_out_of_bounds_jump: bool,
+    /// Set when `program_counter` is found outside the loaded ROM's range,
+    /// consumed by [`Self::take_out_of_bounds_jump_event`].
+    out_of_bounds_jump_event: Option<u16>,
+    /// When set, [`Self::tick_timers`] is a no-op. See [`Self::set_timers_frozen`].
+    timers_frozen: bool,
 }
 
 // All chip 8 programs start at 0x200 because historically, the intepreter itself was stored in the first 512 bytes
 const START_ADDR: u16 = 0x200;
 
+/// Public alias for [`START_ADDR`], the standard address CHIP-8 programs are
+/// loaded at and start executing from.
+pub const START_ADDRESS: u16 = START_ADDR;
+
+/// The most a ROM can be and still fit in [`MemoryModel::Classic`]'s RAM after
+/// [`START_ADDRESS`], e.g. for a frontend validating a ROM before calling
+/// [`Chip8::load`]. XO-CHIP's larger memory model can fit more; see
+/// [`MemoryModel::ram_size`] for that limit instead.
+pub const MAX_ROM_SIZE: usize = MemoryModel::Classic.ram_size() - START_ADDR as usize;
+
+/// Magic bytes at the start of a [`Chip8::save_state_to_path`] file, to reject
+/// unrelated files early instead of misparsing them.
+const SAVE_STATE_MAGIC: &[u8; 4] = b"C8ST";
+/// Layout version of the save-state format. Bump alongside a layout change and
+/// branch on it in [`Chip8::load_state_from_path`] if old saves need to keep loading.
+const SAVE_STATE_VERSION: u8 = 3;
+
+/// Magic bytes at the start of a [`Chip8::save_replay_to_path`] file.
+const REPLAY_MAGIC: &[u8; 4] = b"C8RP";
+/// Layout version of the replay format. Bump alongside a layout change and
+/// branch on it in [`Chip8::load_replay_from_path`] if old replays need to keep loading.
+const REPLAY_VERSION: u8 = 1;
+
+// How many instructions run for every 60Hz timer decrement in `run_until_timer_tick`
+const TIMER_TICK_INSTRUCTION_BUDGET: usize = 10;
+
+/// Mask an opcode down to the nibbles that identify its instruction family, dropping
+/// register indices and immediates so opcode coverage can be grouped by category.
+fn opcode_category(opcode: u16) -> u16 {
+    match (opcode & 0xF000) >> 12 {
+        0x0 => opcode & 0xF0FF,
+        0x5 | 0x8 | 0x9 => opcode & 0xF00F,
+        0xE | 0xF => opcode & 0xF0FF,
+        _ => opcode & 0xF000,
+    }
+}
+
+/// Pack a [`Quirks`] into two bytes for [`Chip8::save_state_to_path`]: the six
+/// bool flags as individual bits of byte 0, then [`Fx0aKeyOrder`] as bit 6 and
+/// [`FlagWriteOrder`] as bit 7 of byte 0, then [`ShiftQuirk`] as bit 0,
+/// [`LoadStoreQuirk`] as bit 1, `quiet_beep_at_one` as bit 2, and
+/// [`StackOverflowBehavior`] as bit 3 of byte 1.
+fn encode_quirks(quirks: &Quirks) -> [u8; 2] {
+    let byte0 = (quirks.logic_reset_vf as u8)
+        | (quirks.allow_sys_noop as u8) << 1
+        | (quirks.schip_collision as u8) << 2
+        | (quirks.wrap_x as u8) << 3
+        | (quirks.wrap_y as u8) << 4
+        | (quirks.strict_undefined_forms as u8) << 5
+        | ((quirks.fx0a_key_order == Fx0aKeyOrder::MostRecent) as u8) << 6
+        | ((quirks.flag_write_order == FlagWriteOrder::FlagFirst) as u8) << 7;
+    let byte1 = (quirks.shift_quirk == ShiftQuirk::UseVy) as u8
+        | ((quirks.load_store_quirk == LoadStoreQuirk::Increment) as u8) << 1
+        | (quirks.quiet_beep_at_one as u8) << 2
+        | ((quirks.stack_overflow_behavior == StackOverflowBehavior::Ignore) as u8) << 3;
+    [byte0, byte1]
+}
+
+/// The inverse of [`encode_quirks`].
+fn decode_quirks(bytes: [u8; 2]) -> Quirks {
+    let [byte0, byte1] = bytes;
+    Quirks {
+        logic_reset_vf: byte0 & 1 != 0,
+        allow_sys_noop: byte0 & (1 << 1) != 0,
+        schip_collision: byte0 & (1 << 2) != 0,
+        wrap_x: byte0 & (1 << 3) != 0,
+        wrap_y: byte0 & (1 << 4) != 0,
+        strict_undefined_forms: byte0 & (1 << 5) != 0,
+        fx0a_key_order: if byte0 & (1 << 6) != 0 { Fx0aKeyOrder::MostRecent } else { Fx0aKeyOrder::LowestIndex },
+        flag_write_order: if byte0 & (1 << 7) != 0 { FlagWriteOrder::FlagFirst } else { FlagWriteOrder::FlagLast },
+        shift_quirk: if byte1 & 1 != 0 { ShiftQuirk::UseVy } else { ShiftQuirk::UseVx },
+        load_store_quirk: if byte1 & (1 << 1) != 0 { LoadStoreQuirk::Increment } else { LoadStoreQuirk::Unchanged },
+        quiet_beep_at_one: byte1 & (1 << 2) != 0,
+        stack_overflow_behavior: if byte1 & (1 << 3) != 0 { StackOverflowBehavior::Ignore } else { StackOverflowBehavior::Error },
+    }
+}
+
+/// Hash arbitrary bytes with the same hasher [`Chip8::display_hash`] uses, so a
+/// ROM's identity can be compared cheaply without keeping the whole buffer around.
+fn hash_bytes(data: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The inverse of [`Chip8::encode_replay`].
+fn decode_replay(rom: &[u8], bytes: &[u8]) -> io::Result<Replay> {
+    fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> io::Result<&'a [u8]> {
+        let slice = bytes
+            .get(*pos..*pos + n)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated replay"))?;
+        *pos += n;
+        Ok(slice)
+    }
+
+    let mut pos = 0usize;
+    if take(bytes, &mut pos, 4)? != REPLAY_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "not a chip8 replay"));
+    }
+    if take(bytes, &mut pos, 1)?[0] != REPLAY_VERSION {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported replay version"));
+    }
+    let seed = u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap());
+    let rom_hash = u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap());
+    if rom_hash != hash_bytes(rom) {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "replay was recorded against a different ROM"));
+    }
+    let entry_count = u32::from_le_bytes(take(bytes, &mut pos, 4)?.try_into().unwrap()) as usize;
+    let mut input_log = Vec::with_capacity(entry_count);
+    for _ in 0..entry_count {
+        let cycle = u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap());
+        let key = take(bytes, &mut pos, 1)?[0] as usize;
+        let pressed = take(bytes, &mut pos, 1)?[0] != 0;
+        input_log.push((cycle, key, pressed));
+    }
+
+    Ok(Replay { seed, input_log })
+}
+
+/// Approximate relative cycle cost of each instruction family on a real COSMAC VIP,
+/// keyed by [`opcode_category`]. The VIP didn't execute every instruction in the
+/// same number of cycles - flow control and RAM-heavy opcodes cost more than a
+/// simple register load - so a frontend that wants authentic VIP pacing needs more
+/// than a flat instructions-per-frame count. Costs are relative, not calibrated to
+/// real microseconds.
+const VIP_INSTRUCTION_COST_TABLE: &[(u16, u32)] = &[
+    (0x00E0, 24), // CLS - clearing the whole display
+    (0x00EE, 10), // RET
+    (0x1000, 12), // JP addr
+    (0x2000, 14), // CALL addr
+    (0xB000, 12), // JP V0, addr
+    (0xF029, 10), // LD F, Vx
+    (0xF033, 20), // LD B, Vx - three RAM writes plus BCD conversion
+    (0xF055, 18), // LD [I], Vx - register-count-dependent RAM writes
+    (0xF065, 18), // LD Vx, [I]
+];
+
+/// Cost of a single opcode from [`VIP_INSTRUCTION_COST_TABLE`], falling back to a
+/// flat baseline for anything not listed. `Dxyn` is handled separately since its
+/// real cost scales with the sprite height `n`.
+fn instruction_cycle_cost(opcode: u16) -> u32 {
+    if (opcode & 0xF000) == 0xD000 {
+        let n = (opcode & 0x000F) as u32;
+        return 68 + n * 8;
+    }
+    let category = opcode_category(opcode);
+    VIP_INSTRUCTION_COST_TABLE
+        .iter()
+        .find(|&&(cat, _)| cat == category)
+        .map(|&(_, cost)| cost)
+        .unwrap_or(9)
+}
+
 impl Chip8 {
     pub fn new() -> Self {
+        Self::with_memory_model(MemoryModel::Classic)
+    }
+
+    /// Construct a `Chip8` with a specific amount of addressable RAM. XO-CHIP
+    /// programs need [`MemoryModel::XoChip`] to reach beyond the classic 4K.
+    pub fn with_memory_model(memory_model: MemoryModel) -> Self {
+        let seed: u64 = rand::random();
         let mut chip8 = Self {
             program_counter: START_ADDR,
-            ram: [0; RAM_SIZE],
-            screen: [false; SCREEN_WIDTH * SCREEN_HEIGHT],
+            ram: vec![0; memory_model.ram_size()],
+            screen: vec![false; SCREEN_WIDTH * SCREEN_HEIGHT],
             v_registers: [0; V_REGISTERS],
             i_register: 0,
             delay_timer_register: 0,
@@ -60,19 +514,387 @@ impl Chip8 {
             stack_pointer: 0,
             stack: [0; STACK_SIZE],
             key_states: [false; NUM_KEYS],
+            timer_accumulator: Duration::ZERO,
+            last_collision_count: 0,
+            waiting_for_key: false,
+            opcode_histogram: HashMap::new(),
+            quirks: Quirks::default(),
+            cycle_count: 0,
+            recording_input: false,
+            input_log: Vec::new(),
+            vip_cycle_estimate: 0,
+            pending_memory_error: None,
+            detect_self_modify: false,
+            self_modify_event: None,
+            pixel_changes: 0,
+            start_addr: START_ADDR,
+            screen_plane2: vec![false; SCREEN_WIDTH * SCREEN_HEIGHT],
+            selected_planes: 0b01,
+            screen_dirty: false,
+            key_press_order: Vec::new(),
+            input_source: None,
+            cycle_clock_accumulator: 0,
+            record_register_writes: false,
+            register_write_log: Vec::new(),
+            timer_hz: 60,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            halted: false,
+            cls_event: false,
+            random_source: None,
+            beep_start_value: None,
+            last_beep_frames: None,
+            rom_len: 0,
+            record_branch_decisions: false,
+            branch_log: Vec::new(),
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            watchpoint_hit: None,
+            hi_res: false,
+            record_timer_log: false,
+            timer_log: Vec::new(),
+            detect_out_of_bounds_jump: false,
+            out_of_bounds_jump_event: None,
+            timers_frozen: false,
         };
         chip8.ram[..FONTSET_SIZE].copy_from_slice(&FONTSET);
         chip8
     }
 
-    /// Tick and execute an instruction
-    pub fn tick(&mut self) {
+    /// Construct a `Chip8` with a non-default set of behavioral quirks enabled.
+    pub fn with_quirks(quirks: Quirks) -> Self {
+        let mut chip8 = Self::new();
+        chip8.quirks = quirks;
+        chip8
+    }
+
+    /// Construct a `Chip8` that loads and starts execution at `start_addr` instead
+    /// of the standard 0x200 (e.g. 0x600 for ETI-660 ROMs). The fontset still lives
+    /// in low RAM regardless of where the program is placed.
+    pub fn with_start_addr(start_addr: u16) -> Self {
+        let mut chip8 = Self::new();
+        chip8.start_addr = start_addr;
+        chip8.program_counter = start_addr;
+        chip8
+    }
+
+    /// Construct a `Chip8` whose `Cxkk` random draws are reproducible: the same
+    /// seed always produces the same sequence of random bytes. Pair with
+    /// [`Self::save_replay_to_path`]/[`Self::load_replay_from_path`] for
+    /// deterministic, shareable replays.
+    pub fn with_seed(seed: u64) -> Self {
+        let mut chip8 = Self::new();
+        chip8.seed = seed;
+        chip8.rng = StdRng::seed_from_u64(seed);
+        chip8
+    }
+
+    /// Mutable access to the active quirks, for reconfiguring after construction.
+    pub fn quirks_mut(&mut self) -> &mut Quirks {
+        &mut self.quirks
+    }
+
+    /// The seed backing `Cxkk`'s random draws, e.g. to bundle into a [`Replay`].
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Fetch, decode, and execute one instruction, reporting what happened as a
+    /// single [`TickOutcome`] - see its docs for precedence when more than one
+    /// applies. Existing narrower accessors ([`Self::take_memory_error`],
+    /// [`Self::is_halted`], [`Self::is_waiting_for_key`]) still work exactly as
+    /// before for callers that only care about one condition.
+    pub fn tick(&mut self) -> TickOutcome {
+        if self.halted {
+            return TickOutcome::Halted;
+        }
+        self.waiting_for_key = false;
+        self.watchpoint_hit = None;
+        let pc = self.program_counter;
         let opcode = self.fetch();
-        self.execute(opcode);
+        self.advance_pc();
+        self.vip_cycle_estimate += instruction_cycle_cost(opcode) as u64;
+        if let Err(err) = self.execute(opcode) {
+            self.pending_memory_error = Some(err);
+            return TickOutcome::Error(err);
+        }
+        if self.detect_out_of_bounds_jump {
+            self.check_out_of_bounds_jump();
+        }
+        self.cycle_count += 1;
+        if self.halted {
+            return TickOutcome::Halted;
+        }
+        if self.breakpoints.contains(&pc) {
+            return TickOutcome::Breakpoint(pc);
+        }
+        if let Some(addr) = self.watchpoint_hit {
+            return TickOutcome::Watchpoint(addr);
+        }
+        if self.waiting_for_key {
+            return TickOutcome::WaitingForKey;
+        }
+        TickOutcome::Normal
+    }
+
+    /// Add `addr` to the set of breakpoint addresses (see [`TickOutcome::Breakpoint`]).
+    /// A no-op if it's already present.
+    pub fn add_breakpoint(&mut self, addr: u16) {
+        if !self.breakpoints.contains(&addr) {
+            self.breakpoints.push(addr);
+        }
+    }
+
+    /// Remove `addr` from the set of breakpoint addresses. A no-op if it wasn't set.
+    pub fn remove_breakpoint(&mut self, addr: u16) {
+        self.breakpoints.retain(|&bp| bp != addr);
+    }
+
+    /// The current set of breakpoint addresses, in the order they were added.
+    pub fn breakpoints(&self) -> &[u16] {
+        &self.breakpoints
+    }
+
+    /// Add `addr` to the set of watched write addresses (see [`TickOutcome::Watchpoint`]).
+    /// A no-op if it's already present.
+    pub fn add_watchpoint(&mut self, addr: u16) {
+        if !self.watchpoints.contains(&addr) {
+            self.watchpoints.push(addr);
+        }
+    }
+
+    /// Remove `addr` from the set of watched write addresses. A no-op if it wasn't set.
+    pub fn remove_watchpoint(&mut self, addr: u16) {
+        self.watchpoints.retain(|&wp| wp != addr);
+    }
+
+    /// The current set of watched write addresses, in the order they were added.
+    pub fn watchpoints(&self) -> &[u16] {
+        &self.watchpoints
+    }
+
+    /// If `write_addr` is a watched address, record it for this tick's [`TickOutcome::Watchpoint`].
+    fn check_watchpoint(&mut self, write_addr: u16) {
+        if self.watchpoints.contains(&write_addr) {
+            self.watchpoint_hit = Some(write_addr);
+        }
+    }
+
+    /// Take and clear the most recent error raised by opcode execution (e.g.
+    /// `Fx33` with `I` near the end of RAM), if any. Lets a frontend surface
+    /// a clean diagnostic for a buggy ROM instead of the emulator panicking.
+    pub fn take_memory_error(&mut self) -> Option<Chip8Error> {
+        self.pending_memory_error.take()
+    }
+
+    /// True if `00E0` (CLS) executed since the last call, then resets to false.
+    /// A ghosting or trail-effect renderer can use this to reset its intensity
+    /// buffer in sync with the emulated screen clearing, instead of drifting out
+    /// of sync with a stale trail left over from before the clear.
+    pub fn take_cls_event(&mut self) -> bool {
+        std::mem::take(&mut self.cls_event)
+    }
+
+    /// Peek at the most recent opcode-execution error without clearing it, for a
+    /// frontend that wants to inspect the machine's state after a failed tick
+    /// (e.g. in a debugger) without disturbing [`Self::take_memory_error`]'s
+    /// one-shot semantics. Since every error-returning handler validates its
+    /// addresses before mutating anything, the V registers, `I` and RAM are
+    /// exactly as they were before the failing tick; only the program counter
+    /// has already moved past the failing instruction.
+    pub fn last_error(&self) -> Option<&Chip8Error> {
+        self.pending_memory_error.as_ref()
+    }
+
+    /// True if the last tick re-executed `Fx0A` because no key was pressed yet.
+    /// Frontends can use this to avoid busy-spinning the CPU while waiting for input.
+    pub fn is_waiting_for_key(&self) -> bool {
+        self.waiting_for_key
+    }
+
+    /// True once SUPER-CHIP's `00FD` (EXIT) has run. [`Self::tick`] becomes a
+    /// no-op from that point on; a frontend should stop calling it or show an
+    /// "exited" message instead.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// True after SUPER-CHIP's `00FF` (HIGH); false after `00FE` (LOW) or by
+    /// default. A frontend should recompute its render target from
+    /// [`Self::display_width`]/[`Self::display_height`] each frame so a mode
+    /// switch mid-ROM takes effect live instead of requiring a restart.
+    pub fn is_hi_res(&self) -> bool {
+        self.hi_res
+    }
+
+    /// The active display width in pixels: [`HI_RES_WIDTH`] in hi-res mode,
+    /// [`SCREEN_WIDTH`] otherwise. [`Self::get_display`]'s pixel buffer is
+    /// always exactly `display_width() * display_height()` long, resized by
+    /// [`Self::op_low_res`]/[`Self::op_hi_res`] on every mode switch.
+    pub fn display_width(&self) -> usize {
+        if self.hi_res { HI_RES_WIDTH } else { SCREEN_WIDTH }
+    }
+
+    /// The active display height in pixels: [`HI_RES_HEIGHT`] in hi-res mode,
+    /// [`SCREEN_HEIGHT`] otherwise. See [`Self::display_width`]'s note on the
+    /// pixel buffer's size.
+    pub fn display_height(&self) -> usize {
+        if self.hi_res { HI_RES_HEIGHT } else { SCREEN_HEIGHT }
+    }
+
+    /// Accumulate real elapsed time and run enough ticks to keep timers decrementing
+    /// at [`Self::set_timer_hz`]'s rate (60Hz by default), independent of the
+    /// frontend's render frame rate. `ipf` controls how many CPU ticks run per
+    /// timer decrement.
+    pub fn update(&mut self, elapsed: Duration, ipf: u32) {
+        let timer_period = Duration::from_nanos(1_000_000_000 / self.timer_hz as u64);
+        self.timer_accumulator += elapsed;
+        while self.timer_accumulator >= timer_period {
+            for _ in 0..ipf {
+                self.tick();
+            }
+            self.tick_timers();
+            self.timer_accumulator -= timer_period;
+        }
+    }
+
+    /// Change the rate [`Self::update`] decrements the delay/sound timers at, in Hz.
+    /// The CPU tick rate (`ipf`) is unaffected - this only stretches or compresses
+    /// how much real time each timer decrement corresponds to, e.g. for slow-motion
+    /// study at 30Hz or fast-forwarding at 120Hz.
+    pub fn set_timer_hz(&mut self, hz: u32) {
+        self.timer_hz = hz;
+    }
+
+    /// Run `cycles` CPU ticks, decrementing timers once every `cpu_clock_hz / 60`
+    /// cycles instead of once per call. Unlike [`Self::update`] (wall-clock time)
+    /// or [`Self::tick_timers`] (once per frontend frame), this ties timer speed
+    /// to the CPU's own clock rate, so a ROM's timing stays correct regardless
+    /// of how many ticks the frontend batches into a frame.
+    pub fn run_cycle_clocked(&mut self, cycles: usize, cpu_clock_hz: u64) {
+        let cycles_per_timer_tick = (cpu_clock_hz / 60).max(1);
+        for _ in 0..cycles {
+            self.tick();
+            self.cycle_clock_accumulator += 1;
+            if self.cycle_clock_accumulator >= cycles_per_timer_tick {
+                self.tick_timers();
+                self.cycle_clock_accumulator -= cycles_per_timer_tick;
+            }
+        }
+    }
+
+    /// Run ticks until it's time for the next 60Hz timer decrement (or `max_cycles`
+    /// is reached, whichever comes first), then decrement the timers once. Returns
+    /// the number of instructions actually run. A cleaner main-loop primitive than
+    /// hardcoding an instructions-per-frame inner loop in the frontend.
+    pub fn run_until_timer_tick(&mut self, max_cycles: usize) -> usize {
+        let budget = TIMER_TICK_INSTRUCTION_BUDGET.min(max_cycles);
+        for _ in 0..budget {
+            self.tick();
+        }
+        self.tick_timers();
+        budget
+    }
+
+    /// Run ticks until the program counter reaches `addr` or `max_cycles` is
+    /// reached, whichever comes first. A one-shot alternative to setting a
+    /// permanent breakpoint when the debugger just wants to seek somewhere.
+    pub fn run_to(&mut self, addr: u16, max_cycles: usize) -> RunToResult {
+        for _ in 0..max_cycles {
+            if self.program_counter == addr {
+                return RunToResult::Reached;
+            }
+            self.tick();
+            if let Some(err) = self.take_memory_error() {
+                return RunToResult::Error(err);
+            }
+        }
+        if self.program_counter == addr {
+            RunToResult::Reached
+        } else {
+            RunToResult::CycleLimitReached
+        }
+    }
+
+    /// Tick past the next instruction, running through a `2nnn` CALL's whole
+    /// subroutine instead of stepping into it, or up to `max_cycles` ticks if the
+    /// matching `RET` never returns the stack to its prior depth. If the next
+    /// instruction isn't a CALL, this is just a single [`Self::tick`].
+    pub fn step_over(&mut self, max_cycles: usize) -> Result<(), Chip8Error> {
+        let is_call = self.fetch() & 0xF000 == 0x2000;
+        let call_depth = self.stack_depth();
+
+        for _ in 0..max_cycles {
+            self.tick();
+            if let Some(err) = self.take_memory_error() {
+                return Err(err);
+            }
+            if !is_call || self.stack_depth() <= call_depth {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Run up to `max_cycles` ticks, stopping early on a memory error. For fuzzing
+    /// and CI harnesses that need a run to terminate even if the ROM loops
+    /// forever - unlike [`Self::run_to`] there's no target address to reach, so
+    /// this either exhausts `max_cycles` ([`TickOutcome::LimitReached`]), stops
+    /// when `00FD` halts the interpreter ([`TickOutcome::Halted`]), or fails.
+    pub fn run_with_limit(&mut self, max_cycles: u64) -> Result<TickOutcome, Chip8Error> {
+        for _ in 0..max_cycles {
+            self.tick();
+            if let Some(err) = self.take_memory_error() {
+                return Err(err);
+            }
+            if self.is_halted() {
+                return Ok(TickOutcome::Halted);
+            }
+        }
+        Ok(TickOutcome::LimitReached)
+    }
+
+    /// Run one full frame: `ipf` CPU ticks followed by a single timer decrement.
+    /// Distinct from single-instruction stepping ([`Self::tick`]) - a debugger can
+    /// wire this to a dedicated "next frame" key while paused, to advance game
+    /// logic at its natural granularity instead of one opcode at a time. Ticks
+    /// stop early if one errors or halts the interpreter, but the timer decrement
+    /// always runs. Returns the last tick's [`TickOutcome`].
+    pub fn step_frame(&mut self, ipf: usize) -> Result<TickOutcome, Chip8Error> {
+        let mut outcome = TickOutcome::Normal;
+        for _ in 0..ipf {
+            outcome = self.tick();
+            if let TickOutcome::Error(err) = outcome {
+                return Err(err);
+            }
+            if outcome == TickOutcome::Halted {
+                break;
+            }
+        }
+        self.tick_timers();
+        Ok(outcome)
+    }
+
+    /// Tick until a `CLS`/`Dxyn` touches the display or `max_cycles` elapses,
+    /// returning whether a draw occurred. Far faster than single-stepping
+    /// through the non-drawing code most ROMs spend most of their time in.
+    pub fn step_to_next_draw(&mut self, max_cycles: usize) -> bool {
+        self.take_screen_dirty(); // discard any dirty flag left over from before this call
+        for _ in 0..max_cycles {
+            self.tick();
+            if self.take_screen_dirty() {
+                return true;
+            }
+        }
+        false
     }
 
     /// Runs every frame - count down timers
     pub fn tick_timers(&mut self) {
+        if self.timers_frozen {
+            return;
+        }
+
         if self.delay_timer_register > 0 {
             self.delay_timer_register -= 1;
         }
@@ -81,314 +903,3185 @@ impl Chip8 {
             self.sound_timer_register -= 1;
             if self.sound_timer_register == 0 {
                 // BEEP
+                if let Some(start) = self.beep_start_value.take() {
+                    self.last_beep_frames = Some(start as u32);
+                }
             }
         }
+
+        if self.record_timer_log {
+            self.timer_log.push(TimerLogEntry {
+                cycle: self.cycle_count,
+                delay: self.delay_timer_register,
+                sound: self.sound_timer_register,
+            });
+        }
+    }
+
+    /// Freeze or unfreeze the delay/sound timers: while frozen,
+    /// [`Self::tick_timers`] is a no-op, so a debugger single-stepping through
+    /// `tick` can inspect timer-gated game state without it decaying in the
+    /// background.
+    pub fn set_timers_frozen(&mut self, frozen: bool) {
+        self.timers_frozen = frozen;
+    }
+
+    /// How many frames the most recently completed beep lasted, i.e. the sound
+    /// timer's value when it was last set via `Fx18` before counting down to
+    /// zero. `None` until a beep has actually finished at least once.
+    pub fn last_beep_frames(&self) -> Option<u32> {
+        self.last_beep_frames
+    }
+
+    /// Whether the buzzer should currently be sounding. Any nonzero sound
+    /// timer beeps by default; with [`Quirks::quiet_beep_at_one`] enabled the
+    /// timer must be at least 2, matching hardware whose buzzer doesn't
+    /// audibly sound for a single frame.
+    pub fn is_beeping(&self) -> bool {
+        if self.quirks.quiet_beep_at_one {
+            self.sound_timer_register >= 2
+        } else {
+            self.sound_timer_register > 0
+        }
+    }
+
+    /// Mask an address down into the current RAM size, wrapping around like a real
+    /// interpreter's address bus rather than panicking on a malformed ROM that runs
+    /// off the end of RAM.
+    fn wrap_pc(&self, addr: u32) -> u16 {
+        (addr % self.ram.len() as u32) as u16
+    }
+
+    /// Read the opcode at `program_counter` without advancing it. Split out from
+    /// PC advance so `Fx0A` and display-wait can re-execute the same instruction
+    /// next tick just by skipping [`Self::advance_pc`], instead of fetching then
+    /// rewinding.
+    fn fetch(&self) -> u16 {
+        let pc = self.program_counter as usize;
+        let high_byte = self.ram[pc] as u16;
+        let low_byte = self.ram[(pc + 1) % self.ram.len()] as u16;
+        (high_byte << 8) | low_byte
+    }
+
+    /// Move `program_counter` past the two-byte instruction just fetched.
+    fn advance_pc(&mut self) {
+        self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
     }
 
-    fn fetch(&mut self) -> u16 {
-        let high_byte = self.ram[self.program_counter as usize] as u16;
-        let low_byte = self.ram[(self.program_counter + 1) as usize] as u16;
-        let opcode = (high_byte << 8) | low_byte;
-        self.program_counter += 2;
-        opcode
+    /// Move `program_counter` back to the instruction just advanced past, e.g.
+    /// so `Fx0A` re-fetches itself while waiting for a key. Wrapping mirror of
+    /// [`Self::advance_pc`].
+    fn rewind_pc(&mut self) {
+        self.program_counter = self.wrap_pc(self.program_counter as u32 + self.ram.len() as u32 - 2);
     }
 
-    fn execute(&mut self, opcode: u16) {
+    /// Decode `opcode` into its family/operands and dispatch to the `op_*` handler
+    /// for that instruction. Kept as a thin dispatcher so each opcode's behavior
+    /// can be read, tested, and modified in isolation.
+    fn execute(&mut self, opcode: u16) -> Result<(), Chip8Error> {
         let digit1 = (opcode & 0xF000) >> 12;
         let digit2 = (opcode & 0x0F00) >> 8;
         let digit3 = (opcode & 0x00F0) >> 4;
         let digit4 = opcode & 0x000F;
+        let x = digit2 as usize;
+        let y = digit3 as usize;
+        let n = digit4;
+        let kk = (opcode & 0x00FF) as u8;
+        let nnn = opcode & 0x0FFF;
+        *self.opcode_histogram.entry(opcode_category(opcode)).or_insert(0) += 1;
         match (digit1, digit2, digit3, digit4) {
             // 0000 - NOP - no operation
-            (0x0, 0x0, 0x0, 0x0) => return,
-            // 00E0 - CLS - Clear the display
-            (0x0, 0x0, 0xE, 0x0) => self.screen = [false; SCREEN_WIDTH * SCREEN_HEIGHT],
+            (0x0, 0x0, 0x0, 0x0) => (),
+            // 00E0 - CLS - Clear the display (only the currently selected bitplanes)
+            (0x0, 0x0, 0xE, 0x0) => self.op_cls(),
             // 00EE - RET - Return from a subroutine
-            (0x0, 0x0, 0xE, 0xE) => {
-                let ret_addr = self.pop();
-                self.program_counter = ret_addr;
-            }
+            (0x0, 0x0, 0xE, 0xE) => self.op_ret(),
+            // 00FD - SCHIP - EXIT - Halt the interpreter
+            (0x0, 0x0, 0xF, 0xD) => self.op_exit(),
+            // 00FE - SCHIP - LOW - Switch to low-res (64x32) mode
+            (0x0, 0x0, 0xF, 0xE) => self.op_low_res(),
+            // 00FF - SCHIP - HIGH - Switch to high-res (128x64) mode
+            (0x0, 0x0, 0xF, 0xF) => self.op_hi_res(),
+            // 0nnn - SYS addr - Call machine code routine (ignored on modern interpreters)
+            (0x0, _, _, _) if self.quirks.allow_sys_noop => (),
             // 1nnn - JP addr - Jump to location nnn
-            (0x1, _, _, _) => {
-                let nnn = opcode & 0x0FFF;
-                self.program_counter = nnn;
-            }
+            (0x1, _, _, _) => self.op_jp(nnn),
             // 2nnn- CALL addr - Call subroutine at nnn
-            (0x2, _, _, _) => {
-                let nnn = opcode & 0x0FFF;
-                self.push(self.program_counter);
-                self.program_counter = nnn;
-            }
+            (0x2, _, _, _) => return self.op_call(nnn),
             // 3xkk - SE Vx, byte - Skip next instruction if Vx = kk
             (0x3, _, _, _) => {
-                let register = digit2 as usize;
-                let byte = (opcode & 0x00FF) as u8;
-                if self.v_registers[register] == byte {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_se_vx_byte(x, kk);
+                self.log_branch(opcode, pc_before);
             }
             // 4xkk - SNE Vx, byte - Skip next instruction if Vx != kk
             (0x4, _, _, _) => {
-                let register = digit2 as usize;
-                let byte = (opcode & 0x00FF) as u8;
-                if self.v_registers[register] != byte {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_sne_vx_byte(x, kk);
+                self.log_branch(opcode, pc_before);
             }
             // 5xy0 - SE Vx, Vy - Skip next instruction if Vx = Vy
             (0x5, _, _, 0x0) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                if self.v_registers[x] == self.v_registers[y] {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_se_vx_vy(x, y);
+                self.log_branch(opcode, pc_before);
             }
+            // 5xy2 - XO-CHIP - Store Vx..Vy (inclusive, either direction) to memory starting at I
+            (0x5, _, _, 0x2) => return self.op_ld_range_i_vx(x, y),
+            // 5xy3 - XO-CHIP - Load Vx..Vy (inclusive, either direction) from memory starting at I
+            (0x5, _, _, 0x3) => return self.op_ld_vx_range_i(x, y),
+            // 5xy_ - undefined form (only 5xy0/5xy2/5xy3 are defined)
+            (0x5, _, _, _) => return self.reject_undefined_form(opcode),
             // 6xkk - LD Vx, byte - Set Vx = kk
-            (0x6, _, _, _) => {
-                let register = digit2 as usize;
-                let byte = (opcode & 0x00FF) as u8;
-                self.v_registers[register] = byte;
-            }
+            (0x6, _, _, _) => self.op_ld_vx_byte(x, kk),
             // 7xkk - ADD Vx, byte - Set Vx = Vx + kk
-            (0x7, _, _, _) => {
-                let register = digit2 as usize;
-                let value = (opcode & 0x00FF) as u8;
-                self.v_registers[register] = self.v_registers[register].wrapping_add(value);
-            }
+            (0x7, _, _, _) => self.op_add_vx_byte(x, kk),
             // 8xy0 - LD Vx, Vy - Store value of register Vy in register Vx
-            (0x8, _, _, 0x0) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                self.v_registers[x] = self.v_registers[y];
-            }
+            (0x8, _, _, 0x0) => self.op_ld_vx_vy(x, y),
             // 8xy1 - OR Vx, Vy - Set Vx = Vx Or Vy
-            (0x8, _, _, 0x1) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                self.v_registers[x] = self.v_registers[x] | self.v_registers[y];
-            }
+            (0x8, _, _, 0x1) => self.op_or(x, y),
             // 8xy2 - AND Vx, Vy - Set Vx = Vx AND Vy
-            (0x8, _, _, 0x2) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                self.v_registers[x] = self.v_registers[x] & self.v_registers[y];
-            }
+            (0x8, _, _, 0x2) => self.op_and(x, y),
             // 8xy3 - XOR Vx, Vy - Set Vx = Vx XOR Vy
-            (0x8, _, _, 0x3) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                self.v_registers[x] = self.v_registers[x] ^ self.v_registers[y];
-            }
+            (0x8, _, _, 0x3) => self.op_xor(x, y),
             // 8xy4 - ADD Vx, Vy - Set Vx = Vx + Vy, Set VF = carry
-            (0x8, _, _, 0x4) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                let (new_vx, carry) = self.v_registers[x].overflowing_add(self.v_registers[y]);
-                let new_vf = if carry { 1 } else { 0 };
-                self.v_registers[x] = new_vx;
-                self.v_registers[0xF] = new_vf;
-            }
+            (0x8, _, _, 0x4) => self.op_add_vx_vy(x, y),
             // 8xy5 - SUB
-            (0x8, _, _, 0x5) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                let (new_vx, borrow) = self.v_registers[x].overflowing_sub(self.v_registers[y]);
-                self.v_registers[x] = new_vx;
-                self.v_registers[0xF] = if borrow { 0 } else { 1 };
-            }
-            // 8xy6 - SHR Vx - Set VX = Vx >> 1
-            (0x8, _, _, 0x6) => {
-                let x = digit2 as usize;
-                let lsb = self.v_registers[x] & 1;
-                self.v_registers[x] >>= 1;
-                self.v_registers[0xF] = lsb;
-            }
+            (0x8, _, _, 0x5) => self.op_sub(x, y),
+            // 8xy6 - SHR Vx {, Vy} - Set VX = Vx >> 1 (or Vy >> 1 under ShiftQuirk::UseVy)
+            (0x8, _, _, 0x6) => self.op_shr(x, y),
             // 8xy7 - SUBM Vx, Vy - Set Vx = V, Set VF = NOT borrow
-            (0x8, _, _, 0x7) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                let (new_vx, borrow) = self.v_registers[y].overflowing_sub(self.v_registers[x]);
-                self.v_registers[x] = new_vx;
-                self.v_registers[0xF] = if borrow { 0 } else { 1 };
-            }
-            // 8xyE - SHL Vx - Set Vx = Vx SHL 1
-            (0x8, _, _, 0xE) => {
-                let x = digit2 as usize;
-                let msb = (self.v_registers[x] >> 7) & 1;
-                self.v_registers[x] <<= 1;
-                self.v_registers[0xF] = msb;
-            }
+            (0x8, _, _, 0x7) => self.op_subn(x, y),
+            // 8xyE - SHL Vx {, Vy} - Set Vx = Vx SHL 1 (or Vy SHL 1 under ShiftQuirk::UseVy)
+            (0x8, _, _, 0xE) => self.op_shl(x, y),
             // 9xy0 - SNE Vx, Vy - Skip next instruction if Vx != Vy
             (0x9, _, _, 0) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                if self.v_registers[x] != self.v_registers[y] {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_sne_vx_vy(x, y);
+                self.log_branch(opcode, pc_before);
             }
+            // 9xy_ - undefined form (only 9xy0 is defined)
+            (0x9, _, _, _) => return self.reject_undefined_form(opcode),
             // Annn - LD I, addr - Set I = nnn
-            (0xA, _, _, _) => {
-                let nnn = opcode & 0x0FFF;
-                self.i_register = nnn;
-            }
+            (0xA, _, _, _) => self.op_ld_i(nnn),
             // Bnnn - JP V0, addr - Jump to location nnn + V0
-            (0xB, _, _, _) => {
-                let nnn = opcode & 0x0FFF;
-                self.program_counter = nnn + (self.v_registers[0] as u16);
-            }
+            (0xB, _, _, _) => self.op_jp_v0(nnn),
             // Cxkk - RND Vx, byte - Set Vx = random byte AND kk
-            (0xC, _, _, _) => {
-                let x = digit2 as usize;
-                let kk = (opcode & 0x00FF) as u8;
-                let byte: u8 = random();
-                self.v_registers[x] = byte & kk;
-            }
+            (0xC, _, _, _) => self.op_rnd(x, kk),
             // Dxyn - DRW Vx, Vy, nibble - Display n-byte sprite starting at memory location I at (Vx, Vy), set VF = collision
-            (0xD, _, _, _) => {
-                let x = digit2 as usize;
-                let y = digit3 as usize;
-                let n = digit4;
-
-                // (x, y) coordinate for sprite
-                let x_coord = self.v_registers[x] as u16;
-                let y_coord = self.v_registers[y] as u16;
-
-                let mut flipped = false;
-                for y in 0..n {
-                    let addr = self.i_register + y as u16;
-                    let pixels = self.ram[addr as usize];
-
-                    for x in 0..8 {
-                        // Use a mask to fetch current pixel's bit. Only flip if a 1
-                        if (pixels & (0b1000_0000 >> x)) != 0 {
-                            // Sprites should wrap around screen, so apply modulo
-                            let x = (x_coord + x) as usize % SCREEN_WIDTH;
-                            let y = (y_coord + y) as usize % SCREEN_HEIGHT;
-
-                            // Get our pixel's index for our 1D screen array
-                            let idx = x + SCREEN_WIDTH * y;
-                            // Check if we're about to flip the pixel and set
-                            flipped |= self.screen[idx];
-                            self.screen[idx] ^= true;
-                        }
-                    }
-                }
-
-                if flipped {
-                    self.v_registers[0xF] = 1;
-                } else {
-                    self.v_registers[0xF] = 0;
-                }
-            }
+            (0xD, _, _, _) => self.op_drw(x, y, n),
             // Ex9E - SKP Vx - Skip next instruction if key with the value of Vx is pressed
             (0xE, _, 0x9, 0xE) => {
-                let x = digit2 as usize;
-                if self.key_states[self.v_registers[x] as usize] {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_skp(x);
+                self.log_branch(opcode, pc_before);
             }
             // ExA1 - SKNP Vx - Skip next instruction if key with the value of Vx is not pressed
             (0xE, _, 0xA, 0x1) => {
-                let x = digit2 as usize;
-                if !self.key_states[self.v_registers[x] as usize] {
-                    self.program_counter += 2;
-                }
+                let pc_before = self.program_counter;
+                self.op_sknp(x);
+                self.log_branch(opcode, pc_before);
             }
             // Fx07 - LD Vx, DT - Set Vx = delay timer value
-            (0xF, _, 0x0, 0x7) => {
-                let x = digit2 as usize;
-                self.v_registers[x] = self.delay_timer_register;
-            }
+            (0xF, _, 0x0, 0x7) => self.op_ld_vx_dt(x),
             // Fx0A - LD Vx, K - Wait for a key press, store the value of the key in Vx
-            (0xF, _, 0x0, 0xA) => {
-                let x = digit2 as usize;
-                let mut pressed = false;
-                for i in 0..self.key_states.len() {
-                    if self.key_states[i] {
-                        self.v_registers[x] = i as u8;
-                        pressed = true;
-                        break;
-                    }
-                }
-
-                if !pressed {
-                    self.program_counter -= 2;
-                }
-            }
+            (0xF, _, 0x0, 0xA) => self.op_ld_vx_k(x),
             // Fx15 - LD DT, Vx - Set delay timer = Vx
-            (0xF, _, 0x1, 0x5) => {
-                let x = digit2 as usize;
-                self.delay_timer_register = self.v_registers[x];
-            }
+            (0xF, _, 0x1, 0x5) => self.op_ld_dt_vx(x),
             // Fx18 - LD ST, Vx - Set sound timer = Vx
-            (0xF, _, 0x1, 0x8) => {
-                let x = digit2 as usize;
-                self.sound_timer_register = self.v_registers[x];
-            }
+            (0xF, _, 0x1, 0x8) => self.op_ld_st_vx(x),
             // Fx1E - ADD I, Vx - Set I = I + Vx
-            (0xF, _, 0x1, 0xE) => {
-                let x = digit2 as usize;
-                self.i_register = self.i_register.wrapping_add(self.v_registers[x] as u16);
-            }
+            (0xF, _, 0x1, 0xE) => self.op_add_i_vx(x),
             // Fx29 - LD F, Vx - Set I = location of sprite for digit Vx
-            (0xF, _, 2, 9) => {
-                let x = digit2 as usize;
-                let c = self.v_registers[x] as u16;
-                self.i_register = c * 5;
-            }
+            (0xF, _, 2, 9) => self.op_ld_f_vx(x),
             // Fx33 - LD B, Vx - Store BCD representation of Vx in memory locations I, I+1, I+2
-            (0xF, _, 0x3, 0x3) => {
-                let x = digit2 as usize;
-                let vx = self.v_registers[x];
-                let hundreds = vx / 100;
-                let tens = (vx / 10) % 10;
-                let digits = vx % 10;
-                self.ram[self.i_register as usize] = hundreds;
-                self.ram[(self.i_register + 1) as usize] = tens;
-                self.ram[(self.i_register + 2) as usize] = digits;
-            }
+            (0xF, _, 0x3, 0x3) => return self.op_ld_b_vx(x),
             // Fx55 - LD [I], Vx - Store registers V0 through Vx in memory starting at location I
-            (0xF, _, 0x5, 0x5) => {
-                let x = digit2 as usize;
-                for i in 0..=x {
-                    self.ram[self.i_register as usize + i] = self.v_registers[i];
-                }
-            }
+            (0xF, _, 0x5, 0x5) => return self.op_ld_i_vx(x),
             // Fx65 - LD Vx, [I] - Read registers V0 through Vx from memory starting at location I
-            (0xF, _, 0x6, 0x5) => {
-                let x = digit2 as usize;
-                for i in 0..=x {
-                    self.v_registers[i] = self.ram[self.i_register as usize + i];
-                }
-            }
+            (0xF, _, 0x6, 0x5) => return self.op_ld_vx_i(x),
+            // Fn01 - XO-CHIP - Select bitplane(s) n for subsequent CLS/DRW operations
+            (0xF, n, 0x0, 0x1) => self.op_select_planes(n as u8),
             _ => panic!(
                 "Invalid opcode: {:#06x} at address {}",
                 opcode, self.program_counter
             ),
         }
+        Ok(())
     }
 
-    fn push(&mut self, val: u16) {
-        self.stack[self.stack_pointer as usize] = val;
-        self.stack_pointer += 1
+    fn op_cls(&mut self) {
+        self.cls_event = true;
+        if self.selected_planes & 0b01 != 0 {
+            self.pixel_changes += self.screen.iter().filter(|&&lit| lit).count() as u32;
+            self.screen.iter_mut().for_each(|pixel| *pixel = false);
+            self.screen_dirty = true;
+        }
+        if self.selected_planes & 0b10 != 0 {
+            self.pixel_changes += self.screen_plane2.iter().filter(|&&lit| lit).count() as u32;
+            self.screen_plane2.iter_mut().for_each(|pixel| *pixel = false);
+            self.screen_dirty = true;
+        }
     }
 
-    fn pop(&mut self) -> u16 {
-        self.stack_pointer -= 1;
-        self.stack[self.stack_pointer as usize]
+    /// Resize and clear both bitplanes to match the current
+    /// [`Self::display_width`]/[`Self::display_height`], regardless of which
+    /// planes `Fn01` has selected - a resolution switch changes the whole
+    /// display's shape, not just the active planes' contents.
+    fn resize_display(&mut self) {
+        self.cls_event = true;
+        let len = self.display_width() * self.display_height();
+        self.pixel_changes += self.screen.iter().filter(|&&lit| lit).count() as u32;
+        self.pixel_changes += self.screen_plane2.iter().filter(|&&lit| lit).count() as u32;
+        self.screen = vec![false; len];
+        self.screen_plane2 = vec![false; len];
+        self.screen_dirty = true;
     }
 
-    pub fn get_display(&self) -> &[bool] {
-        &self.screen
+    fn op_ret(&mut self) {
+        let ret_addr = self.pop();
+        self.program_counter = self.wrap_pc(ret_addr as u32);
     }
 
-    pub fn keypress(&mut self, idx: usize, pressed: bool) {
-        self.key_states[idx] = pressed;
+    fn op_exit(&mut self) {
+        self.halted = true;
     }
 
-    pub fn load(&mut self, data: &[u8]) {
-        let start = START_ADDR as usize;
-        let end = (START_ADDR as usize) + data.len();
-        self.ram[start..end].copy_from_slice(data);
+    /// Switching resolution also resizes and clears the display, matching real
+    /// SCHIP interpreters.
+    fn op_low_res(&mut self) {
+        self.hi_res = false;
+        self.resize_display();
+    }
+
+    /// Switching resolution also resizes and clears the display, matching real
+    /// SCHIP interpreters.
+    fn op_hi_res(&mut self) {
+        self.hi_res = true;
+        self.resize_display();
+    }
+
+    fn op_jp(&mut self, nnn: u16) {
+        self.program_counter = self.wrap_pc(nnn as u32);
+    }
+
+    fn op_call(&mut self, nnn: u16) -> Result<(), Chip8Error> {
+        if self.push(self.program_counter)? {
+            self.program_counter = self.wrap_pc(nnn as u32);
+        }
+        Ok(())
+    }
+
+    fn op_se_vx_byte(&mut self, x: usize, kk: u8) {
+        if self.v_registers[x] == kk {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_sne_vx_byte(&mut self, x: usize, kk: u8) {
+        if self.v_registers[x] != kk {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_se_vx_vy(&mut self, x: usize, y: usize) {
+        if self.v_registers[x] == self.v_registers[y] {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_ld_vx_byte(&mut self, x: usize, kk: u8) {
+        self.write_v(x, kk);
+    }
+
+    fn op_add_vx_byte(&mut self, x: usize, kk: u8) {
+        let new_vx = self.v_registers[x].wrapping_add(kk);
+        self.write_v(x, new_vx);
+    }
+
+    fn op_ld_vx_vy(&mut self, x: usize, y: usize) {
+        self.write_v(x, self.v_registers[y]);
+    }
+
+    fn op_or(&mut self, x: usize, y: usize) {
+        let new_vx = self.v_registers[x] | self.v_registers[y];
+        self.write_v(x, new_vx);
+        self.apply_logic_quirk();
+    }
+
+    fn op_and(&mut self, x: usize, y: usize) {
+        let new_vx = self.v_registers[x] & self.v_registers[y];
+        self.write_v(x, new_vx);
+        self.apply_logic_quirk();
+    }
+
+    fn op_xor(&mut self, x: usize, y: usize) {
+        let new_vx = self.v_registers[x] ^ self.v_registers[y];
+        self.write_v(x, new_vx);
+        self.apply_logic_quirk();
+    }
+
+    /// Write an `8xy_` handler's destination register and its VF flag, in the
+    /// order [`Quirks::flag_write_order`] selects. Only observable when `x` is
+    /// VF itself, in which case one of the two writes clobbers the other.
+    fn write_vx_and_flag(&mut self, x: usize, result: u8, flag: u8) {
+        match self.quirks.flag_write_order {
+            FlagWriteOrder::FlagLast => {
+                self.write_v(x, result);
+                self.write_v(0xF, flag);
+            }
+            FlagWriteOrder::FlagFirst => {
+                self.write_v(0xF, flag);
+                self.write_v(x, result);
+            }
+        }
+    }
+
+    fn op_add_vx_vy(&mut self, x: usize, y: usize) {
+        let (new_vx, carry) = self.v_registers[x].overflowing_add(self.v_registers[y]);
+        self.write_vx_and_flag(x, new_vx, if carry { 1 } else { 0 });
+    }
+
+    fn op_sub(&mut self, x: usize, y: usize) {
+        let (new_vx, borrow) = self.v_registers[x].overflowing_sub(self.v_registers[y]);
+        self.write_vx_and_flag(x, new_vx, if borrow { 0 } else { 1 });
+    }
+
+    fn op_shr(&mut self, x: usize, y: usize) {
+        let source = match self.quirks.shift_quirk {
+            ShiftQuirk::UseVx => self.v_registers[x],
+            ShiftQuirk::UseVy => self.v_registers[y],
+        };
+        let lsb = source & 1;
+        self.write_vx_and_flag(x, source >> 1, lsb);
+    }
+
+    fn op_subn(&mut self, x: usize, y: usize) {
+        let (new_vx, borrow) = self.v_registers[y].overflowing_sub(self.v_registers[x]);
+        self.write_vx_and_flag(x, new_vx, if borrow { 0 } else { 1 });
+    }
+
+    fn op_shl(&mut self, x: usize, y: usize) {
+        let source = match self.quirks.shift_quirk {
+            ShiftQuirk::UseVx => self.v_registers[x],
+            ShiftQuirk::UseVy => self.v_registers[y],
+        };
+        let msb = (source >> 7) & 1;
+        self.write_vx_and_flag(x, source << 1, msb);
+    }
+
+    fn op_sne_vx_vy(&mut self, x: usize, y: usize) {
+        if self.v_registers[x] != self.v_registers[y] {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_ld_i(&mut self, nnn: u16) {
+        self.write_i(nnn);
+    }
+
+    // Reads V0 fresh at call time, so a preceding arithmetic opcode's VF side
+    // effect (e.g. `8xy4`'s carry flag) can never leak into the jump offset -
+    // V0 and VF are always distinct registers here. Some ROMs rely on this
+    // exact ordering when an `8xy_` arithmetic op immediately precedes `Bnnn`.
+    fn op_jp_v0(&mut self, nnn: u16) {
+        self.program_counter = self.wrap_pc(nnn as u32 + self.v_registers[0] as u32);
+    }
+
+    fn op_rnd(&mut self, x: usize, kk: u8) {
+        let byte = self.next_random_byte();
+        self.write_v(x, byte & kk);
+    }
+
+    fn op_drw(&mut self, x: usize, y: usize, n: u16) {
+        // (x, y) coordinate for sprite
+        let x_coord = self.v_registers[x] as u16;
+        let y_coord = self.v_registers[y] as u16;
+
+        let mut flipped = false;
+        let mut collision_count = 0u32;
+        let mut collided_rows = 0u32;
+        let mut clipped_rows = 0u32;
+        for plane in 0..2 {
+            if self.selected_planes & (1 << plane) == 0 {
+                continue;
+            }
+            let plane_result = self.draw_sprite_into_plane(plane, x_coord, y_coord, n);
+            flipped |= plane_result.flipped;
+            collision_count += plane_result.collision_count;
+            collided_rows += plane_result.collided_rows;
+            clipped_rows += plane_result.clipped_rows;
+        }
+        self.last_collision_count = collision_count;
+
+        if self.quirks.schip_collision {
+            // VF counts collided rows plus bottom-clipped rows, not just 0/1.
+            self.write_v(0xF, (collided_rows + clipped_rows) as u8);
+        } else if flipped {
+            self.write_v(0xF, 1);
+        } else {
+            self.write_v(0xF, 0);
+        }
+    }
+
+    fn op_skp(&mut self, x: usize) {
+        if self.key_pressed(self.v_registers[x] as usize) {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_sknp(&mut self, x: usize) {
+        if !self.key_pressed(self.v_registers[x] as usize) {
+            self.program_counter = self.wrap_pc(self.program_counter as u32 + 2);
+        }
+    }
+
+    fn op_ld_vx_dt(&mut self, x: usize) {
+        self.write_v(x, self.delay_timer_register);
+    }
+
+    fn op_ld_vx_k(&mut self, x: usize) {
+        let key = match self.quirks.fx0a_key_order {
+            Fx0aKeyOrder::LowestIndex => (0..self.key_states.len()).find(|&i| self.key_pressed(i)),
+            Fx0aKeyOrder::MostRecent => {
+                self.key_press_order.iter().rev().copied().find(|&i| self.key_pressed(i))
+            }
+        };
+
+        match key {
+            Some(i) => self.write_v(x, i as u8),
+            None => {
+                self.rewind_pc();
+                self.waiting_for_key = true;
+            }
+        }
+    }
+
+    fn op_ld_dt_vx(&mut self, x: usize) {
+        self.delay_timer_register = self.v_registers[x];
+    }
+
+    fn op_ld_st_vx(&mut self, x: usize) {
+        self.sound_timer_register = self.v_registers[x];
+        if self.sound_timer_register > 0 {
+            self.beep_start_value = Some(self.sound_timer_register);
+        }
+    }
+
+    fn op_add_i_vx(&mut self, x: usize) {
+        let new_i = self.i_register.wrapping_add(self.v_registers[x] as u16);
+        self.write_i(new_i);
+    }
+
+    fn op_ld_f_vx(&mut self, x: usize) {
+        let c = self.v_registers[x] as u16;
+        self.write_i(c * 5);
+    }
+
+    fn op_ld_b_vx(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.i_register as usize + 2 >= self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.i_register, len: 3 });
+        }
+        self.check_self_modify(self.i_register);
+        self.check_watchpoint(self.i_register);
+        let vx = self.v_registers[x];
+        let hundreds = vx / 100;
+        let tens = (vx / 10) % 10;
+        let digits = vx % 10;
+        self.ram[self.i_register as usize] = hundreds;
+        self.ram[(self.i_register + 1) as usize] = tens;
+        self.ram[(self.i_register + 2) as usize] = digits;
+        Ok(())
+    }
+
+    fn op_ld_i_vx(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.i_register as usize + x >= self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.i_register, len: x + 1 });
+        }
+        self.check_self_modify(self.i_register);
+        self.check_watchpoint(self.i_register);
+        for i in 0..=x {
+            self.ram[self.i_register as usize + i] = self.v_registers[i];
+        }
+        if self.quirks.load_store_quirk == LoadStoreQuirk::Increment {
+            self.write_i(self.wrap_pc(self.i_register as u32 + x as u32 + 1));
+        }
+        Ok(())
+    }
+
+    fn op_ld_vx_i(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.i_register as usize + x >= self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.i_register, len: x + 1 });
+        }
+        for i in 0..=x {
+            let byte = self.ram[self.i_register as usize + i];
+            self.write_v(i, byte);
+        }
+        if self.quirks.load_store_quirk == LoadStoreQuirk::Increment {
+            self.write_i(self.wrap_pc(self.i_register as u32 + x as u32 + 1));
+        }
+        Ok(())
+    }
+
+    /// XO-CHIP `5xy2` - store Vx..Vy (inclusive) to memory starting at I, walking
+    /// backward from x to y if `x > y`. Unlike classic `Fx55`, the range doesn't
+    /// have to start at V0 and I is left unchanged afterward.
+    fn op_ld_range_i_vx(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
+        let count = x.abs_diff(y) + 1;
+        if self.i_register as usize + count > self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.i_register, len: count });
+        }
+        self.check_self_modify(self.i_register);
+        self.check_watchpoint(self.i_register);
+        for offset in 0..count {
+            let reg = if x <= y { x + offset } else { x - offset };
+            self.ram[self.i_register as usize + offset] = self.v_registers[reg];
+        }
+        Ok(())
+    }
+
+    /// XO-CHIP `5xy3` - the load counterpart of [`Self::op_ld_range_i_vx`].
+    fn op_ld_vx_range_i(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
+        let count = x.abs_diff(y) + 1;
+        if self.i_register as usize + count > self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.i_register, len: count });
+        }
+        for offset in 0..count {
+            let reg = if x <= y { x + offset } else { x - offset };
+            let byte = self.ram[self.i_register as usize + offset];
+            self.write_v(reg, byte);
+        }
+        Ok(())
+    }
+
+    fn op_select_planes(&mut self, n: u8) {
+        self.selected_planes = n & 0b11;
+    }
+
+    /// Which opcode categories have executed so far, keyed by a masked opcode
+    /// that identifies the instruction family (registers/immediates stripped out).
+    pub fn opcode_coverage(&self) -> &HashMap<u16, u64> {
+        &self.opcode_histogram
+    }
+
+    /// On the original COSMAC VIP, the logical ops also reset VF as a side effect.
+    fn apply_logic_quirk(&mut self) {
+        if self.quirks.logic_reset_vf {
+            self.write_v(0xF, 0);
+        }
+    }
+
+    /// Handle an undefined `5xy_`/`9xy_` form: an error under
+    /// `Quirks::strict_undefined_forms`, otherwise a silent no-op.
+    fn reject_undefined_form(&mut self, opcode: u16) -> Result<(), Chip8Error> {
+        if self.quirks.strict_undefined_forms {
+            Err(Chip8Error::UnknownOpcode { opcode })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// If self-modify detection is on and `write_addr` falls in the already-executed
+    /// code region (below `program_counter`), record it for [`Chip8::take_self_modify_event`].
+    fn check_self_modify(&mut self, write_addr: u16) {
+        if self.detect_self_modify && write_addr < self.program_counter {
+            self.self_modify_event = Some((write_addr, self.program_counter));
+        }
+    }
+
+    /// If `program_counter` has landed outside the loaded ROM (e.g. into the
+    /// font region or past the end of the program), record it for
+    /// [`Chip8::take_out_of_bounds_jump_event`].
+    fn check_out_of_bounds_jump(&mut self) {
+        let program_start = self.start_addr as u32;
+        let program_end = program_start + self.rom_len as u32;
+        if !(program_start..program_end).contains(&(self.program_counter as u32)) {
+            self.out_of_bounds_jump_event = Some(self.program_counter);
+        }
+    }
+
+    /// Push `val` onto the call stack, returning whether it actually happened.
+    /// A full stack either raises [`Chip8Error::StackOverflow`] or - under
+    /// [`StackOverflowBehavior::Ignore`] - silently returns `Ok(false)`, so
+    /// [`Self::op_call`] can skip the jump too and leave the whole `CALL` a no-op.
+    fn push(&mut self, val: u16) -> Result<bool, Chip8Error> {
+        if self.stack_pointer as usize >= STACK_SIZE {
+            return match self.quirks.stack_overflow_behavior {
+                StackOverflowBehavior::Error => Err(Chip8Error::StackOverflow),
+                StackOverflowBehavior::Ignore => Ok(false),
+            };
+        }
+        self.stack[self.stack_pointer as usize] = val;
+        self.stack_pointer += 1;
+        Ok(true)
+    }
+
+    fn pop(&mut self) -> u16 {
+        self.stack_pointer -= 1;
+        self.stack[self.stack_pointer as usize]
+    }
+
+    /// Plane 0 of the display, borrowed from the live `Chip8`. This view is only
+    /// valid as long as the borrow lives, so it can't be sent to another thread
+    /// or window - use [`Self::packed_display`] for an owned snapshot that can.
+    pub fn get_display(&self) -> &[bool] {
+        &self.screen
+    }
+
+    /// Render plane 0 of the display as a multiline string, `#` for a lit pixel
+    /// and ` ` for an unlit one, wrapped every [`Self::display_width`] characters.
+    /// Handy for dumping the screen in a failing test's assertion output.
+    pub fn display_string(&self) -> String {
+        self.screen
+            .chunks(self.display_width())
+            .map(|row| row.iter().map(|&pixel| if pixel { '#' } else { ' ' }).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The display for a single XO-CHIP bitplane: `0` is the classic single-plane
+    /// display (identical to [`Chip8::get_display`]), `1` is the second plane added
+    /// by the `Fn01` select-bitplane opcode. A frontend combines the two into the
+    /// standard four-color XO-CHIP palette by treating them as a 2-bit index per
+    /// pixel: `00` background, `01` plane 0 only, `10` plane 1 only, `11` both set.
+    pub fn display_plane(&self, plane: usize) -> &[bool] {
+        match plane {
+            0 => &self.screen,
+            1 => &self.screen_plane2,
+            _ => panic!("Invalid plane index: {plane}"),
+        }
+    }
+
+    /// Draw an `n`-byte sprite from `I` into bitplane `plane` (0 or 1) at `(x_coord,
+    /// y_coord)`, applying the same wrap/clip quirks as `Dxyn`. Returns whether any
+    /// pixel was erased, the collision count, and the collided/clipped row counts
+    /// used by the SCHIP collision-counting quirk.
+    fn draw_sprite_into_plane(
+        &mut self,
+        plane: usize,
+        x_coord: u16,
+        y_coord: u16,
+        n: u16,
+    ) -> SpriteDrawResult {
+        let width = self.display_width();
+        let height = self.display_height();
+        let mut flipped = false;
+        let mut collision_count = 0u32;
+        let mut collided_rows = 0u32;
+        let mut clipped_rows = 0u32;
+        for row in 0..n {
+            let screen_y = y_coord + row;
+            // Rows that run off the bottom edge are clipped instead of
+            // wrapped when the schip_collision quirk (SCHIP) is on or the
+            // wrap_y quirk is off (wrapping rows is the native behavior).
+            if screen_y as usize >= height && (self.quirks.schip_collision || !self.quirks.wrap_y)
+            {
+                clipped_rows += 1;
+                continue;
+            }
+            let y = screen_y as usize % height;
+
+            // Wrap within the configured memory model's RAM instead of
+            // overflowing/panicking when I is near the top of address space,
+            // which large XO-CHIP ROMs can do legitimately.
+            let addr = (self.i_register as usize + row as usize) % self.ram.len();
+            let pixels = self.ram[addr];
+            let row_collided = if pixels != 0 && x_coord as usize + 8 <= width {
+                // Fast path: the whole row lands on-screen with no wrap or clip
+                // possible mid-row, so XOR all 8 columns as a single byte instead
+                // of branching per pixel.
+                let row_base = y * width + x_coord as usize;
+                let (row_collision_count, row_collided) =
+                    self.draw_row_fast(plane, row_base, pixels);
+                collision_count += row_collision_count;
+                flipped |= row_collided;
+                self.pixel_changes += pixels.count_ones();
+                self.screen_dirty = true;
+                row_collided
+            } else {
+                let mut row_collided = false;
+                for x in 0..8 {
+                    // Use a mask to fetch current pixel's bit. Only flip if a 1
+                    if (pixels & (0b1000_0000 >> x)) != 0 {
+                        let screen_x = x_coord + x;
+                        // Columns that run off the right edge are clipped instead
+                        // of wrapped when the wrap_x quirk is off.
+                        if screen_x as usize >= width && !self.quirks.wrap_x {
+                            continue;
+                        }
+                        let x = screen_x as usize % width;
+
+                        // Get our pixel's index for our 1D screen array
+                        let idx = x + width * y;
+                        let screen = if plane == 0 { &mut self.screen } else { &mut self.screen_plane2 };
+                        // Check if we're about to flip the pixel and set
+                        if screen[idx] {
+                            flipped = true;
+                            collision_count += 1;
+                            row_collided = true;
+                        }
+                        screen[idx] ^= true;
+                        self.pixel_changes += 1;
+                        self.screen_dirty = true;
+                    }
+                }
+                row_collided
+            };
+            if row_collided {
+                collided_rows += 1;
+            }
+        }
+        SpriteDrawResult { flipped, collision_count, collided_rows, clipped_rows }
+    }
+
+    /// Fast path for [`Self::draw_sprite_into_plane`]: XOR a full, non-wrapping,
+    /// non-clipped row of 8 pixels starting at `row_base` in one pass instead of
+    /// branching per pixel. Returns the number of pixels that collided and
+    /// whether any of them did.
+    fn draw_row_fast(&mut self, plane: usize, row_base: usize, pixels: u8) -> (u32, bool) {
+        let screen = if plane == 0 { &mut self.screen } else { &mut self.screen_plane2 };
+        let row = &mut screen[row_base..row_base + 8];
+        let mut current_mask = 0u8;
+        for (bit, &pixel) in row.iter().enumerate() {
+            if pixel {
+                current_mask |= 0b1000_0000 >> bit;
+            }
+        }
+        let collision_mask = current_mask & pixels;
+        let new_mask = current_mask ^ pixels;
+        for (bit, pixel) in row.iter_mut().enumerate() {
+            *pixel = new_mask & (0b1000_0000 >> bit) != 0;
+        }
+        (collision_mask.count_ones(), collision_mask != 0)
+    }
+
+    /// The number of pixels that were erased (collided) by the most recent `Dxyn`.
+    pub fn last_collision_count(&self) -> u32 {
+        self.last_collision_count
+    }
+
+    /// The current value of the `I` register, e.g. for a frontend debug overlay
+    /// that wants to preview the sprite `I` points at.
+    pub fn i_register(&self) -> u16 {
+        self.i_register
+    }
+
+    /// The current value of the program counter, e.g. for a frontend debug HUD.
+    pub fn program_counter(&self) -> u16 {
+        self.program_counter
+    }
+
+    /// The 16 general-purpose V registers, e.g. for a frontend debug HUD.
+    pub fn v_registers(&self) -> &[u8; V_REGISTERS] {
+        &self.v_registers
+    }
+
+    /// Whether the program counter is on the standard 2-byte instruction boundary.
+    /// CHIP-8 opcodes are 2 bytes wide, but nothing stops a `1nnn`/`2nnn`/`Bnnn`
+    /// jump from landing on an odd address; fetching still reads two consecutive
+    /// bytes from wherever `program_counter` points, so misaligned execution
+    /// "desyncs" the instruction stream rather than crashing.
+    pub fn is_aligned(&self) -> bool {
+        self.program_counter.is_multiple_of(2)
+    }
+
+    /// Total estimated COSMAC VIP cycle cost of every instruction executed so far,
+    /// from [`VIP_INSTRUCTION_COST_TABLE`]. A frontend that wants to pace execution
+    /// to real VIP speed (rather than a flat instructions-per-frame count) can use
+    /// deltas of this value instead of `cycle_count`.
+    pub fn vip_cycle_estimate(&self) -> u64 {
+        self.vip_cycle_estimate
+    }
+
+    /// The current call depth, i.e. the number of return addresses pushed by `CALL`.
+    pub fn stack_depth(&self) -> usize {
+        self.stack_pointer as usize
+    }
+
+    /// The valid (used) portion of the call stack, oldest call first. A clean view
+    /// of nested subroutine calls for a debugger, without the unused trailing slots.
+    pub fn call_stack(&self) -> &[u16] {
+        &self.stack[..self.stack_pointer as usize]
+    }
+
+    /// Like [`Self::call_stack`], but each entry also carries the address of the
+    /// `CALL` that pushed it, for a debugger printing a symbolic-ish trace.
+    pub fn call_frames(&self) -> Vec<CallFrame> {
+        self.call_stack()
+            .iter()
+            .map(|&return_addr| CallFrame {
+                return_addr,
+                call_site: self.wrap_pc(return_addr as u32 + self.ram.len() as u32 - 2),
+            })
+            .collect()
+    }
+
+    /// Label RAM by what's in it - the font, the loaded program, and whatever's
+    /// left over - for a debugger's memory-map view. Regions are returned in
+    /// address order and never overlap; a region is omitted entirely if it's empty
+    /// (e.g. no free space before the program area when it starts right after the font).
+    pub fn memory_regions(&self) -> Vec<MemoryRegion> {
+        let font_end = FONTSET_SIZE;
+        let program_start = self.start_addr as usize;
+        let program_end = program_start + self.rom_len;
+
+        let mut regions = vec![MemoryRegion { kind: MemoryRegionKind::Font, start: 0, end: font_end }];
+        if program_start > font_end {
+            regions.push(MemoryRegion { kind: MemoryRegionKind::Free, start: font_end, end: program_start });
+        }
+        if program_end > program_start {
+            regions.push(MemoryRegion { kind: MemoryRegionKind::Program, start: program_start, end: program_end });
+        }
+        if program_end < self.ram.len() {
+            regions.push(MemoryRegion { kind: MemoryRegionKind::Free, start: program_end, end: self.ram.len() });
+        }
+        regions
+    }
+
+    /// Take a cheap, loggable snapshot of the machine state.
+    pub fn snapshot(&self) -> MachineSnapshot {
+        MachineSnapshot {
+            program_counter: self.program_counter,
+            stack_pointer: self.stack_pointer,
+            i_register: self.i_register,
+            v_registers: self.v_registers,
+            delay_timer: self.delay_timer_register,
+            sound_timer: self.sound_timer_register,
+            packed_display: self.pack_display(),
+        }
+    }
+
+    /// An owned, packed copy of plane 0 of the display (see [`Self::pack_display`]
+    /// for the byte layout), decoupled from `&self`'s lifetime so it can be cloned
+    /// and handed to another thread or window - e.g. a spectator window mirroring
+    /// the display without sharing the mutable `Chip8`. `[u8; 256]` is `Copy` and
+    /// holds no borrowed state, so it's `Send + Sync` on its own; it's just a
+    /// point-in-time copy though, with nothing keeping it in sync with the live
+    /// emulator, so the receiving side must poll this again for each new frame.
+    pub fn packed_display(&self) -> [u8; SCREEN_WIDTH * SCREEN_HEIGHT / 8] {
+        self.pack_display()
+    }
+
+    /// Overwrite the display with a packed frame (see [`Self::packed_display`]
+    /// for the byte layout), without going through `00E0`/`Dxyn`. Lets a test
+    /// seed a starting pattern - e.g. to check `Dxyn` collision detection -
+    /// without first drawing that pattern with a real sprite. Fails with
+    /// [`Chip8Error::WrongPackedDisplayLen`] if `packed` isn't exactly
+    /// `SCREEN_WIDTH * SCREEN_HEIGHT / 8` bytes.
+    pub fn set_display_packed(&mut self, packed: &[u8]) -> Result<(), Chip8Error> {
+        const EXPECTED: usize = SCREEN_WIDTH * SCREEN_HEIGHT / 8;
+        if packed.len() != EXPECTED {
+            return Err(Chip8Error::WrongPackedDisplayLen { expected: EXPECTED, actual: packed.len() });
+        }
+        for (i, pixel) in self.screen.iter_mut().take(SCREEN_WIDTH * SCREEN_HEIGHT).enumerate() {
+            *pixel = packed[i / 8] & (0b1000_0000 >> (i % 8)) != 0;
+        }
+        Ok(())
+    }
+
+    /// Pack the display into 256 bytes, 8 pixels per byte MSB-first in row-major
+    /// order. This packed format predates hi-res mode and stays fixed at
+    /// [`SCREEN_WIDTH`] x [`SCREEN_HEIGHT`] pixels, so in hi-res mode it only
+    /// covers the buffer's first 2048 pixels rather than the full
+    /// [`Self::display_width`] x [`Self::display_height`] display.
+    fn pack_display(&self) -> [u8; SCREEN_WIDTH * SCREEN_HEIGHT / 8] {
+        let mut packed = [0u8; SCREEN_WIDTH * SCREEN_HEIGHT / 8];
+        for (i, &pixel) in self.screen.iter().take(SCREEN_WIDTH * SCREEN_HEIGHT).enumerate() {
+            if pixel {
+                packed[i / 8] |= 0b1000_0000 >> (i % 8);
+            }
+        }
+        packed
+    }
+
+    /// Compare the current display against a 256-byte packed golden frame.
+    pub fn display_matches(&self, expected: &[u8]) -> bool {
+        self.pack_display().as_slice() == expected
+    }
+
+    /// Count how many pixels (bits of the packed frame) differ between the current
+    /// display and a golden frame, useful for a fuzzy-match tolerance in golden tests.
+    pub fn display_diff_count(&self, expected: &[u8]) -> u32 {
+        self.pack_display()
+            .iter()
+            .zip(expected)
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum()
+    }
+
+    /// Hash of the packed display, for compact golden-frame comparisons (e.g. a
+    /// test-ROM runner checking a known-good end state without storing the full
+    /// 256-byte frame).
+    pub fn display_hash(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.pack_display().hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Compare the current display against a 256-byte packed previous frame (see
+    /// [`Self::pack_display`] for the byte layout) and list every pixel that
+    /// changed, as `(x, y, new_value)`, for a remote frontend that only wants to
+    /// send the delta since the last frame it rendered.
+    pub fn diff_display(&self, previous: &[u8]) -> Vec<(usize, usize, bool)> {
+        let current = self.pack_display();
+        let mut changes = Vec::new();
+        for (i, (&cur_byte, &prev_byte)) in current.iter().zip(previous).enumerate() {
+            let mut diff = cur_byte ^ prev_byte;
+            while diff != 0 {
+                let bit = diff.leading_zeros() as usize;
+                let index = i * 8 + bit;
+                changes.push((index % SCREEN_WIDTH, index / SCREEN_WIDTH, cur_byte & (0b1000_0000 >> bit) != 0));
+                diff &= !(0b1000_0000 >> bit);
+            }
+        }
+        changes
+    }
+
+    pub fn keypress(&mut self, idx: usize, pressed: bool) {
+        if self.recording_input {
+            self.input_log.push((self.cycle_count, idx, pressed));
+        }
+        self.key_states[idx] = pressed;
+        if pressed {
+            self.key_press_order.retain(|&k| k != idx);
+            self.key_press_order.push(idx);
+        }
+    }
+
+    /// Enable or disable recording of `keypress` calls (tagged with the cycle they
+    /// occurred on) for later reproduction via [`Chip8::replay_input`].
+    pub fn record_input(&mut self, enabled: bool) {
+        self.recording_input = enabled;
+    }
+
+    /// The recorded `(cycle_count, key, pressed)` log so far.
+    pub fn input_log(&self) -> &[(u64, usize, bool)] {
+        &self.input_log
+    }
+
+    /// Enable or disable recording of every V-register and I-register write
+    /// (tagged with the cycle it happened on), for diffing execution against a
+    /// reference emulator when chasing a desync. Off by default since it adds
+    /// overhead to every arithmetic and load opcode.
+    pub fn record_register_writes(&mut self, enabled: bool) {
+        self.record_register_writes = enabled;
+    }
+
+    /// The recorded `(cycle_count, RegisterWrite)` log so far.
+    pub fn register_write_log(&self) -> &[(u64, RegisterWrite)] {
+        &self.register_write_log
+    }
+
+    /// Enable or disable recording of every skip instruction's decision
+    /// (`3xkk`/`4xkk`/`5xy0`/`9xy0`/`Ex9E`/`ExA1`), for debugging why a ROM's
+    /// control flow went the way it did. Off by default since it adds overhead
+    /// to every one of those opcodes.
+    pub fn record_branch_decisions(&mut self, enabled: bool) {
+        self.record_branch_decisions = enabled;
+    }
+
+    /// The recorded `BranchDecision` log so far.
+    pub fn branch_log(&self) -> &[BranchDecision] {
+        &self.branch_log
+    }
+
+    /// Enable or disable recording a [`TimerLogEntry`] on every
+    /// [`Self::tick_timers`] call, for diagnosing a ROM that misuses the delay
+    /// or sound timer. Off by default since it adds overhead to every frame.
+    pub fn record_timer_log(&mut self, enabled: bool) {
+        self.record_timer_log = enabled;
+    }
+
+    /// The recorded `TimerLogEntry` log so far.
+    pub fn timer_log(&self) -> &[TimerLogEntry] {
+        &self.timer_log
+    }
+
+    /// Log a skip instruction's decision to `branch_log`, if recording is on.
+    /// `pc_before` is `program_counter` right after the instruction was
+    /// fetched and advanced past, but before the skip opcode itself ran -
+    /// comparing it against the current `program_counter` tells whether the
+    /// skip actually took effect.
+    fn log_branch(&mut self, opcode: u16, pc_before: u16) {
+        if !self.record_branch_decisions {
+            return;
+        }
+        let pc = self.wrap_pc(pc_before as u32 + self.ram.len() as u32 - 2);
+        let skipped = self.program_counter != pc_before;
+        self.branch_log.push(BranchDecision { pc, opcode, skipped });
+    }
+
+    /// Write `new` to `Vx`, logging the change to `register_write_log` if recording is on.
+    fn write_v(&mut self, index: usize, new: u8) {
+        let old = self.v_registers[index];
+        self.v_registers[index] = new;
+        if self.record_register_writes {
+            self.register_write_log.push((self.cycle_count, RegisterWrite::V { index, old, new }));
+        }
+    }
+
+    /// Write `new` to `I`, logging the change to `register_write_log` if recording is on.
+    fn write_i(&mut self, new: u16) {
+        let old = self.i_register;
+        self.i_register = new;
+        if self.record_register_writes {
+            self.register_write_log.push((self.cycle_count, RegisterWrite::I { old, new }));
+        }
+    }
+
+    /// Enable or disable self-modifying-code detection: when on, a `Fx55`/`Fx33`
+    /// write to an address below the current `program_counter` (i.e. into the
+    /// already-executed code region) is recorded instead of executing silently.
+    /// Useful when reverse-engineering ROMs that rewrite their own instructions.
+    pub fn detect_self_modify(&mut self, enabled: bool) {
+        self.detect_self_modify = enabled;
+    }
+
+    /// Take and clear the most recent self-modifying-code write detected, if any:
+    /// `(write_addr, program_counter)` at the time of the write.
+    pub fn take_self_modify_event(&mut self) -> Option<(u16, u16)> {
+        self.self_modify_event.take()
+    }
+
+    /// Enable or disable out-of-bounds jump detection: when on, [`Self::tick`]
+    /// checks after every instruction whether `program_counter` has landed
+    /// outside the loaded ROM (e.g. a jump into the font region or past the end
+    /// of the program) and records it instead of silently running whatever
+    /// happens to be in memory there. Useful for catching a runaway ROM.
+    pub fn detect_out_of_bounds_jump(&mut self, enabled: bool) {
+        self.detect_out_of_bounds_jump = enabled;
+    }
+
+    /// Take and clear the most recent out-of-bounds jump detected, if any: the
+    /// `program_counter` value it landed on.
+    pub fn take_out_of_bounds_jump_event(&mut self) -> Option<u16> {
+        self.out_of_bounds_jump_event.take()
+    }
+
+    /// Take and clear the number of pixels toggled by `CLS`/`Dxyn` since the last
+    /// call. Lets a frontend log draw-heavy frames without instrumenting its own
+    /// render loop.
+    pub fn take_pixel_changes(&mut self) -> u32 {
+        std::mem::take(&mut self.pixel_changes)
+    }
+
+    /// Take and clear the display dirty flag: `true` if `CLS`/`Dxyn` touched the
+    /// display since the last call. A frontend that copies the display buffer
+    /// across an expensive boundary (e.g. JS/WASM) can skip the copy entirely
+    /// on frames where nothing changed.
+    pub fn take_screen_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.screen_dirty)
+    }
+
+    /// Run the machine, applying each `(cycle_count, key, pressed)` entry from a
+    /// previously recorded log at the matching cycle, until the last entry's cycle
+    /// has been reached. Combined with a fixed RNG seed this makes a run deterministic.
+    pub fn replay_input(&mut self, log: &[(u64, usize, bool)]) {
+        let Some(&last_cycle) = log.iter().map(|(cycle, _, _)| cycle).max() else {
+            return;
+        };
+
+        let mut next = 0;
+        while self.cycle_count <= last_cycle {
+            while let Some(&(cycle, key, pressed)) = log.get(next) {
+                if cycle != self.cycle_count {
+                    break;
+                }
+                self.keypress(key, pressed);
+                next += 1;
+            }
+            self.tick();
+        }
+    }
+
+    /// Whether the key at `idx` is currently held down.
+    pub fn is_key_pressed(&self, idx: usize) -> bool {
+        self.key_states[idx]
+    }
+
+    /// Replace the whole keypad state in one call, for frontends that poll or
+    /// receive all 16 keys at once (e.g. a netplay input packet).
+    pub fn set_keys(&mut self, states: [bool; NUM_KEYS]) {
+        self.key_states = states;
+    }
+
+    /// Route `Ex9E`/`ExA1`/`Fx0A` through `source` instead of the internal
+    /// `key_states` array. Pass `None` to go back to the internal array.
+    pub fn set_input_source(&mut self, source: Option<Box<dyn InputSource>>) {
+        self.input_source = source;
+    }
+
+    /// The key state opcodes should see: the injected [`InputSource`] if one
+    /// is set, otherwise the internal `key_states` array.
+    fn key_pressed(&self, key: usize) -> bool {
+        match &self.input_source {
+            Some(source) => source.is_pressed(key),
+            None => self.key_states[key],
+        }
+    }
+
+    /// Route `Cxkk` through `source` instead of the internal seeded generator.
+    /// Pass `None` to go back to the internal generator.
+    pub fn set_random_source(&mut self, source: Option<Box<dyn RandomSource>>) {
+        self.random_source = source;
+    }
+
+    /// The random byte `Cxkk` should see: the injected [`RandomSource`] if one
+    /// is set, otherwise the internal seeded generator.
+    fn next_random_byte(&mut self) -> u8 {
+        match &mut self.random_source {
+            Some(source) => source.next_byte(),
+            None => self.rng.gen(),
+        }
+    }
+
+    /// Pack the 16 key states into a bitmask (bit `i` set means key `i` is held),
+    /// the minimal wire format for syncing input between netplay peers.
+    pub fn encode_keys(&self) -> u16 {
+        self.key_states
+            .iter()
+            .enumerate()
+            .fold(0u16, |mask, (i, &pressed)| mask | ((pressed as u16) << i))
+    }
+
+    /// Replace the whole keypad state from a bitmask produced by [`Self::encode_keys`].
+    pub fn apply_key_mask(&mut self, mask: u16) {
+        for (i, state) in self.key_states.iter_mut().enumerate() {
+            *state = (mask & (1 << i)) != 0;
+        }
+    }
+
+    /// Copy a ROM into RAM starting at `start_addr`. Fails with [`Chip8Error::EmptyRom`]
+    /// on a zero-length `data`, [`Chip8Error::OutOfBounds`] if `data` is too big to fit
+    /// before the end of RAM (see [`MAX_ROM_SIZE`] for the classic memory model's limit),
+    /// or [`Chip8Error::OverwritesFontRegion`] if a custom `start_addr` (see
+    /// [`Self::with_start_addr`]) would land the ROM on top of the font region.
+    pub fn load(&mut self, data: &[u8]) -> Result<(), Chip8Error> {
+        if data.is_empty() {
+            return Err(Chip8Error::EmptyRom);
+        }
+        let start = self.start_addr as usize;
+        let end = start + data.len();
+        if start < FONTSET_SIZE {
+            return Err(Chip8Error::OverwritesFontRegion { start: self.start_addr, len: data.len() });
+        }
+        if end > self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr: self.start_addr, len: data.len() });
+        }
+        self.ram[start..end].copy_from_slice(data);
+        self.rom_len = data.len();
+        Ok(())
+    }
+
+    /// Like [`Self::load`], but first strips a leading Octo-style `#`-comment
+    /// header if `data` has one (see [`rom::strip_header`]) - some distribution
+    /// formats prepend metadata before the binary ROM. A plain binary ROM with
+    /// no header loads unchanged.
+    pub fn load_with_header(&mut self, data: &[u8]) -> Result<(), Chip8Error> {
+        self.load(rom::strip_header(data))
+    }
+
+    /// Reset execution state and re-point the program counter at `start_addr`,
+    /// without touching RAM. Unlike constructing a fresh `Chip8`, this re-runs
+    /// whatever ROM is already loaded without needing the original buffer -
+    /// useful for a frontend's "restart" button.
+    ///
+    /// Instrumentation (`opcode_histogram`, `vip_cycle_estimate`, the input log)
+    /// is left alone, since it tracks the session rather than the machine.
+    pub fn restart(&mut self) {
+        self.program_counter = self.start_addr;
+        self.screen = vec![false; SCREEN_WIDTH * SCREEN_HEIGHT];
+        self.screen_plane2 = vec![false; SCREEN_WIDTH * SCREEN_HEIGHT];
+        self.selected_planes = 0b01;
+        self.hi_res = false;
+        self.halted = false;
+        self.v_registers = [0; V_REGISTERS];
+        self.i_register = 0;
+        self.delay_timer_register = 0;
+        self.sound_timer_register = 0;
+        self.stack_pointer = 0;
+        self.stack = [0; STACK_SIZE];
+        self.key_states = [false; NUM_KEYS];
+        self.timer_accumulator = Duration::ZERO;
+        self.last_collision_count = 0;
+        self.waiting_for_key = false;
+        self.pending_memory_error = None;
+        self.self_modify_event = None;
+        self.out_of_bounds_jump_event = None;
+        self.watchpoint_hit = None;
+        self.pixel_changes = 0;
+        self.screen_dirty = false;
+        self.key_press_order.clear();
+        self.cycle_clock_accumulator = 0;
+    }
+
+    /// Serialize the full machine state - RAM, registers, timers, stack, keys, and
+    /// quirks - to `path`, so a frontend can persist and later resume a session
+    /// without keeping the original ROM buffer around. Session instrumentation is
+    /// left out, matching [`Self::restart`]'s scoping: a save state is a snapshot
+    /// of the machine, not the debugging session around it.
+    pub fn save_state_to_path<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.encode_state())
+    }
+
+    /// Restore state previously written by [`Self::save_state_to_path`], replacing
+    /// this instance's state in place. Fails with `InvalidData` if `path` doesn't
+    /// hold a save state this build understands (bad magic, unknown version, or
+    /// truncated data) - the instance is left untouched in that case.
+    pub fn load_state_from_path<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        let bytes = fs::read(path)?;
+        self.decode_state(&bytes)
+    }
+
+    fn encode_state(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(SAVE_STATE_MAGIC);
+        out.push(SAVE_STATE_VERSION);
+        out.extend_from_slice(&(self.ram.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.ram);
+        out.extend_from_slice(&self.program_counter.to_le_bytes());
+        out.extend_from_slice(&self.i_register.to_le_bytes());
+        out.push(self.delay_timer_register);
+        out.push(self.sound_timer_register);
+        out.push(self.stack_pointer);
+        out.extend_from_slice(&self.v_registers);
+        for addr in self.stack {
+            out.extend_from_slice(&addr.to_le_bytes());
+        }
+        for &pressed in &self.key_states {
+            out.push(pressed as u8);
+        }
+        out.extend_from_slice(&self.start_addr.to_le_bytes());
+        out.push(self.selected_planes);
+        out.push(self.hi_res as u8);
+        for &pixel in self.screen.iter().chain(self.screen_plane2.iter()) {
+            out.push(pixel as u8);
+        }
+        out.extend_from_slice(&encode_quirks(&self.quirks));
+        out
+    }
+
+    fn decode_state(&mut self, bytes: &[u8]) -> io::Result<()> {
+        fn take<'a>(bytes: &'a [u8], pos: &mut usize, n: usize) -> io::Result<&'a [u8]> {
+            let slice = bytes
+                .get(*pos..*pos + n)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "truncated save state"))?;
+            *pos += n;
+            Ok(slice)
+        }
+
+        let mut pos = 0usize;
+        if take(bytes, &mut pos, 4)? != SAVE_STATE_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a chip8 save state"));
+        }
+        if take(bytes, &mut pos, 1)?[0] != SAVE_STATE_VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported save state version"));
+        }
+        let ram_len = u32::from_le_bytes(take(bytes, &mut pos, 4)?.try_into().unwrap()) as usize;
+        let ram = take(bytes, &mut pos, ram_len)?.to_vec();
+        let program_counter = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap());
+        let i_register = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap());
+        let delay_timer_register = take(bytes, &mut pos, 1)?[0];
+        let sound_timer_register = take(bytes, &mut pos, 1)?[0];
+        let stack_pointer = take(bytes, &mut pos, 1)?[0];
+        let mut v_registers = [0u8; V_REGISTERS];
+        v_registers.copy_from_slice(take(bytes, &mut pos, V_REGISTERS)?);
+        let mut stack = [0u16; STACK_SIZE];
+        for slot in stack.iter_mut() {
+            *slot = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap());
+        }
+        let mut key_states = [false; NUM_KEYS];
+        for slot in key_states.iter_mut() {
+            *slot = take(bytes, &mut pos, 1)?[0] != 0;
+        }
+        let start_addr = u16::from_le_bytes(take(bytes, &mut pos, 2)?.try_into().unwrap());
+        let selected_planes = take(bytes, &mut pos, 1)?[0];
+        let hi_res = take(bytes, &mut pos, 1)?[0] != 0;
+        // The pixel section's length depends on the mode just read: the buffers
+        // are resized to match on every hi-res mode switch (see `Self::resize_display`).
+        let display_len = if hi_res { HI_RES_WIDTH * HI_RES_HEIGHT } else { SCREEN_WIDTH * SCREEN_HEIGHT };
+        let mut screen = vec![false; display_len];
+        for slot in screen.iter_mut() {
+            *slot = take(bytes, &mut pos, 1)?[0] != 0;
+        }
+        let mut screen_plane2 = vec![false; display_len];
+        for slot in screen_plane2.iter_mut() {
+            *slot = take(bytes, &mut pos, 1)?[0] != 0;
+        }
+        let quirks = decode_quirks(take(bytes, &mut pos, 2)?.try_into().unwrap());
+
+        self.ram = ram;
+        self.program_counter = program_counter;
+        self.i_register = i_register;
+        self.delay_timer_register = delay_timer_register;
+        self.sound_timer_register = sound_timer_register;
+        self.stack_pointer = stack_pointer;
+        self.v_registers = v_registers;
+        self.stack = stack;
+        self.key_states = key_states;
+        self.start_addr = start_addr;
+        self.selected_planes = selected_planes;
+        self.hi_res = hi_res;
+        self.screen = screen;
+        self.screen_plane2 = screen_plane2;
+        self.quirks = quirks;
+
+        // Session instrumentation and in-flight transient flags don't belong to a
+        // save state, matching the fields `restart` leaves for the same reason.
+        self.timer_accumulator = Duration::ZERO;
+        self.last_collision_count = 0;
+        self.waiting_for_key = false;
+        self.pending_memory_error = None;
+        self.self_modify_event = None;
+        self.out_of_bounds_jump_event = None;
+        self.watchpoint_hit = None;
+        self.pixel_changes = 0;
+        self.screen_dirty = false;
+        self.key_press_order.clear();
+        self.cycle_clock_accumulator = 0;
+
+        Ok(())
+    }
+
+    /// Serialize this session's RNG seed, a hash of `rom`, and the recorded
+    /// [`Self::input_log`] to `path` as a portable, verifiable replay. `rom`
+    /// should be the exact bytes passed to [`Self::load`], since the hash is how
+    /// [`Self::load_replay_from_path`] catches a replay being played back
+    /// against the wrong ROM.
+    pub fn save_replay_to_path<P: AsRef<Path>>(&self, rom: &[u8], path: P) -> io::Result<()> {
+        fs::write(path, self.encode_replay(rom))
+    }
+
+    /// Load a replay previously written by [`Self::save_replay_to_path`],
+    /// verifying it was recorded against `rom`. Fails with `InvalidData` if the
+    /// file isn't a replay this build understands, or if the bundled ROM hash
+    /// doesn't match `rom`.
+    pub fn load_replay_from_path<P: AsRef<Path>>(rom: &[u8], path: P) -> io::Result<Replay> {
+        let bytes = fs::read(path)?;
+        decode_replay(rom, &bytes)
+    }
+
+    fn encode_replay(&self, rom: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(REPLAY_MAGIC);
+        out.push(REPLAY_VERSION);
+        out.extend_from_slice(&self.seed.to_le_bytes());
+        out.extend_from_slice(&hash_bytes(rom).to_le_bytes());
+        out.extend_from_slice(&(self.input_log.len() as u32).to_le_bytes());
+        for &(cycle, key, pressed) in &self.input_log {
+            out.extend_from_slice(&cycle.to_le_bytes());
+            out.push(key as u8);
+            out.push(pressed as u8);
+        }
+        out
+    }
+
+    /// Poke raw bytes into RAM at `addr`, for placing test sprites or data without
+    /// executing opcodes.
+    pub fn write_ram(&mut self, addr: u16, bytes: &[u8]) -> Result<(), Chip8Error> {
+        let start = addr as usize;
+        let end = start + bytes.len();
+        if end > self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr, len: bytes.len() });
+        }
+        self.ram[start..end].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Set a V register directly, for test scaffolding. Panics if `idx` is out of range.
+    pub fn set_v_register(&mut self, idx: usize, val: u8) {
+        self.v_registers[idx] = val;
+    }
+
+    /// Set the I register directly, for test scaffolding.
+    pub fn set_i_register(&mut self, val: u16) {
+        self.i_register = val;
+    }
+
+    /// Set the program counter directly, for test scaffolding.
+    pub fn set_program_counter(&mut self, val: u16) {
+        self.program_counter = val;
+    }
+
+    /// Read `len` bytes of RAM starting at `addr`.
+    pub fn read_ram(&self, addr: u16, len: usize) -> Result<Vec<u8>, Chip8Error> {
+        let start = addr as usize;
+        let end = start + len;
+        if end > self.ram.len() {
+            return Err(Chip8Error::OutOfBounds { addr, len });
+        }
+        Ok(self.ram[start..end].to_vec())
+    }
+
+    /// Read the two-byte opcode at `addr` without advancing the program counter,
+    /// combining the bytes the same way [`Self::fetch`] does. The read-only
+    /// counterpart to `fetch`, for disassembly views that shouldn't affect
+    /// execution.
+    pub fn opcode_at(&self, addr: u16) -> Result<u16, Chip8Error> {
+        let bytes = self.read_ram(addr, 2)?;
+        Ok(((bytes[0] as u16) << 8) | bytes[1] as u16)
+    }
+
+    /// A classic `xxd`-style hex dump of `len` bytes of RAM starting at `addr`, 16
+    /// bytes per line with an address column and an ASCII gutter. Handy for bug
+    /// reports and debugger output. The range is clamped to the end of RAM.
+    pub fn dump_ram_range(&self, addr: u16, len: usize) -> String {
+        let start = (addr as usize).min(self.ram.len());
+        let end = (start + len).min(self.ram.len());
+        let mut out = String::new();
+        for (row, chunk) in self.ram[start..end].chunks(16).enumerate() {
+            let line_addr = start + row * 16;
+            out.push_str(&format!("{line_addr:04x}: "));
+            for byte in chunk {
+                out.push_str(&format!("{byte:02x} "));
+            }
+            for _ in chunk.len()..16 {
+                out.push_str("   ");
+            }
+            out.push('|');
+            for &byte in chunk {
+                let printable = (0x20..0x7f).contains(&byte);
+                out.push(if printable { byte as char } else { '.' });
+            }
+            out.push_str("|\n");
+        }
+        out
+    }
+
+    /// Read `rows` bytes of sprite data starting at `addr`, the way `Dxyn` would
+    /// read them from `I`. Handy for pulling a ROM's graphics out for cataloging
+    /// without having to run the ROM to draw them.
+    pub fn extract_sprite(&self, addr: u16, rows: usize) -> Result<Vec<u8>, Chip8Error> {
+        self.read_ram(addr, rows)
+    }
+
+    /// Render sprite bytes as returned by [`Self::extract_sprite`] as `#`/` ` art,
+    /// one row per byte, most significant bit first - the same rendering
+    /// [`Self::display_string`] uses for the live screen.
+    pub fn sprite_to_string(sprite: &[u8]) -> String {
+        sprite
+            .iter()
+            .map(|&byte| {
+                (0..8).map(|bit| if byte & (0b1000_0000 >> bit) != 0 { '#' } else { ' ' }).collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asm::assemble;
+
+    #[test]
+    fn display_matches_compares_against_a_packed_golden_frame() {
+        let rom = assemble("LD I, 0\nLD V0, 0\nLD V1, 0\nDRW V0, V1, 5").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        for _ in 0..4 {
+            chip8.tick();
+        }
+
+        let golden = chip8.pack_display();
+        assert!(chip8.display_matches(&golden));
+        assert_eq!(chip8.display_diff_count(&golden), 0);
+
+        let mut corrupted = golden;
+        corrupted[0] ^= 0xFF;
+        assert!(!chip8.display_matches(&corrupted));
+        assert_eq!(chip8.display_diff_count(&corrupted), 8);
+    }
+
+    #[test]
+    fn diff_display_lists_exactly_the_pixels_that_changed() {
+        let rom = assemble("LD I, 0\nLD V0, 0\nLD V1, 0\nDRW V0, V1, 5").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        let previous = chip8.packed_display();
+        for _ in 0..4 {
+            chip8.tick();
+        }
+
+        let mut diff = chip8.diff_display(&previous);
+        diff.sort();
+
+        let mut expected: Vec<(usize, usize, bool)> = chip8
+            .get_display()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &lit)| lit.then_some((i % SCREEN_WIDTH, i / SCREEN_WIDTH, true)))
+            .collect();
+        expected.sort();
+
+        assert_eq!(diff, expected);
+        assert!(!diff.is_empty());
+        assert_eq!(chip8.diff_display(&chip8.packed_display()), Vec::new());
+    }
+
+    #[test]
+    fn bnnn_reads_v0_fresh_and_ignores_a_preceding_op_s_vf_side_effect() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0x10);
+        chip8.set_v_register(1, 0xFF);
+        chip8.set_v_register(2, 0x02);
+
+        // ADD V1, V2 overflows, setting VF = 1 as a side effect. V0 must stay
+        // exactly what it was set to - VF leaking into it would desync Bnnn.
+        chip8.execute(0x8124).unwrap();
+        assert_eq!(chip8.v_registers[0xF], 1);
+        assert_eq!(chip8.v_registers[0], 0x10);
+
+        chip8.execute(0xB200).unwrap(); // JP V0, 0x200
+        assert_eq!(chip8.program_counter, 0x200 + 0x10);
+    }
+
+    #[test]
+    fn run_cycle_clocked_decrements_timers_every_cpu_clock_over_60_cycles() {
+        let mut chip8 = Chip8::new();
+        chip8.load(&[0x00, 0x00]).unwrap(); // NOP, so ticks don't disturb the timers
+        chip8.delay_timer_register = 5;
+
+        // At a 600Hz CPU clock, one timer decrement happens every 10 cycles.
+        chip8.run_cycle_clocked(9, 600);
+        assert_eq!(chip8.delay_timer_register, 5);
+
+        chip8.run_cycle_clocked(1, 600);
+        assert_eq!(chip8.delay_timer_register, 4);
+
+        chip8.run_cycle_clocked(20, 600);
+        assert_eq!(chip8.delay_timer_register, 2);
+    }
+
+    #[test]
+    fn update_decrements_timers_at_a_fixed_60hz() {
+        let mut chip8 = Chip8::new();
+        chip8.delay_timer_register = 60;
+        chip8.update(Duration::from_millis(100), 0);
+        assert_eq!(chip8.delay_timer_register, 54);
+    }
+
+    #[test]
+    fn set_timer_hz_changes_how_often_update_decrements_timers() {
+        let mut chip8 = Chip8::new();
+        chip8.set_timer_hz(30);
+        chip8.delay_timer_register = 60;
+        chip8.update(Duration::from_millis(100), 0);
+        // 30Hz over 100ms is half as many decrements as the default 60Hz.
+        assert_eq!(chip8.delay_timer_register, 57);
+    }
+
+    #[test]
+    fn last_beep_frames_reports_the_length_of_the_most_recently_finished_beep() {
+        let mut chip8 = Chip8::new();
+        assert_eq!(chip8.last_beep_frames(), None);
+
+        chip8.set_v_register(0, 30);
+        chip8.execute(0xF018).unwrap(); // LD ST, V0
+
+        for _ in 0..29 {
+            chip8.tick_timers();
+            assert_eq!(chip8.last_beep_frames(), None); // still sounding
+        }
+        chip8.tick_timers(); // the 30th tick lands the sound timer on 0
+        assert_eq!(chip8.last_beep_frames(), Some(30));
+    }
+
+    #[test]
+    fn quiet_beep_at_one_quirk_silences_the_buzzer_at_a_sound_timer_of_one() {
+        let mut chip8 = Chip8::with_quirks(Quirks { quiet_beep_at_one: true, ..Default::default() });
+
+        chip8.sound_timer_register = 1;
+        assert!(!chip8.is_beeping());
+
+        chip8.sound_timer_register = 2;
+        assert!(chip8.is_beeping());
+    }
+
+    #[test]
+    fn timer_log_is_off_by_default_and_records_the_countdown_when_enabled() {
+        let mut chip8 = Chip8::new();
+        chip8.delay_timer_register = 2;
+        chip8.sound_timer_register = 3;
+        chip8.tick_timers();
+        assert!(chip8.timer_log().is_empty());
+
+        chip8.record_timer_log(true);
+        chip8.tick_timers();
+        chip8.tick_timers();
+        chip8.tick_timers();
+
+        assert_eq!(
+            chip8.timer_log(),
+            &[
+                TimerLogEntry { cycle: 0, delay: 0, sound: 1 },
+                TimerLogEntry { cycle: 0, delay: 0, sound: 0 },
+                TimerLogEntry { cycle: 0, delay: 0, sound: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn tick_timers_decrements_by_exactly_one_per_call_and_does_not_underflow() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 5);
+        chip8.execute(0xF015).unwrap(); // LD DT, V0
+        chip8.execute(0xF018).unwrap(); // LD ST, V0
+
+        for expected in (0..5).rev() {
+            chip8.tick_timers();
+            assert_eq!(chip8.delay_timer_register, expected);
+            assert_eq!(chip8.sound_timer_register, expected);
+        }
+
+        // A sixth tick must not underflow past zero.
+        chip8.tick_timers();
+        assert_eq!(chip8.delay_timer_register, 0);
+        assert_eq!(chip8.sound_timer_register, 0);
+    }
+
+    #[test]
+    fn set_timers_frozen_makes_tick_timers_a_no_op() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 5);
+        chip8.execute(0xF015).unwrap(); // LD DT, V0
+        chip8.execute(0xF018).unwrap(); // LD ST, V0
+
+        chip8.set_timers_frozen(true);
+        for _ in 0..10 {
+            chip8.tick_timers();
+        }
+        assert_eq!(chip8.delay_timer_register, 5);
+        assert_eq!(chip8.sound_timer_register, 5);
+
+        chip8.set_timers_frozen(false);
+        chip8.tick_timers();
+        assert_eq!(chip8.delay_timer_register, 4);
+        assert_eq!(chip8.sound_timer_register, 4);
+    }
+
+    #[test]
+    fn write_ram_places_a_sprite_that_draws_correctly() {
+        let sprite = [0b1111_0000u8];
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &sprite).unwrap();
+        assert_eq!(chip8.read_ram(0x300, 1).unwrap(), sprite);
+
+        chip8.i_register = 0x300;
+        chip8.execute(0xD001).unwrap(); // DRW V0, V0, 1 - V0/V0 both default to 0
+
+        assert!(chip8.screen[0] && chip8.screen[1] && chip8.screen[2] && chip8.screen[3]);
+        assert!(!chip8.screen[4]);
+    }
+
+    #[test]
+    fn display_string_renders_a_drawn_sprite_as_hashes() {
+        let sprite = [0b1111_0000u8];
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.i_register = 0x300;
+        chip8.execute(0xD001).unwrap(); // DRW V0, V0, 1 - V0/V0 both default to 0
+
+        let display = chip8.display_string();
+        let lines: Vec<&str> = display.lines().collect();
+        assert_eq!(lines.len(), SCREEN_HEIGHT);
+        assert_eq!(&lines[0][..8], "####    ");
+    }
+
+    #[test]
+    fn packed_display_is_an_independent_snapshot_of_a_single_frame() {
+        let sprite = [0b1111_0000u8];
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.i_register = 0x300;
+        chip8.execute(0xD001).unwrap(); // DRW V0, V0, 1
+
+        // Copy stands in for handing the snapshot to a spectator window on another thread.
+        let mirrored = chip8.packed_display();
+
+        chip8.execute(0x00E0).unwrap(); // CLS - clears the live display
+        assert_ne!(chip8.packed_display(), mirrored);
+        assert_eq!(mirrored[0], 0b1111_0000);
+    }
+
+    #[test]
+    fn set_display_packed_seeds_a_pattern_that_an_overlapping_sprite_collides_with() {
+        let mut seed = [0u8; SCREEN_WIDTH * SCREEN_HEIGHT / 8];
+        seed[0] = 0b1111_0000; // top-left 4 pixels of row 0 already lit
+        let mut chip8 = Chip8::new();
+        chip8.set_display_packed(&seed).unwrap();
+
+        let sprite = [0b1100_0000u8]; // overlaps the seeded pixels' leftmost 2 columns
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.i_register = 0x300;
+        chip8.execute(0xD001).unwrap(); // DRW V0, V0, 1 - V0/V0 both default to 0
+
+        assert_eq!(chip8.v_registers[0xF], 1); // collision detected
+        assert_eq!(chip8.packed_display()[0], 0b0011_0000); // XORed: overlap cleared, rest untouched
+    }
+
+    #[test]
+    fn set_display_packed_rejects_a_buffer_of_the_wrong_length() {
+        let mut chip8 = Chip8::new();
+        assert_eq!(
+            chip8.set_display_packed(&[0u8; 10]),
+            Err(Chip8Error::WrongPackedDisplayLen { expected: 256, actual: 10 })
+        );
+    }
+
+    #[test]
+    fn load_rejects_an_empty_rom() {
+        let mut chip8 = Chip8::new();
+        assert_eq!(chip8.load(&[]), Err(Chip8Error::EmptyRom));
+    }
+
+    #[test]
+    fn max_rom_size_is_the_classic_ram_minus_the_start_address() {
+        assert_eq!(MAX_ROM_SIZE, 3584);
+    }
+
+    #[test]
+    fn load_rejects_a_rom_too_big_to_fit_before_the_end_of_ram() {
+        let mut chip8 = Chip8::new();
+        let oversized = vec![0u8; MAX_ROM_SIZE + 1];
+        assert_eq!(
+            chip8.load(&oversized),
+            Err(Chip8Error::OutOfBounds { addr: START_ADDRESS, len: oversized.len() })
+        );
+    }
+
+    #[test]
+    fn load_with_header_strips_a_leading_comment_header_before_loading() {
+        let mut data = b"# title: Test ROM\n".to_vec();
+        data.extend_from_slice(&[0x12, 0x00]); // JP 0x200
+
+        let mut chip8 = Chip8::new();
+        chip8.load_with_header(&data).unwrap();
+
+        assert_eq!(chip8.read_ram(START_ADDR, 2).unwrap(), vec![0x12, 0x00]);
+    }
+
+    #[test]
+    fn write_ram_rejects_out_of_bounds_writes() {
+        let mut chip8 = Chip8::new();
+        let last_addr = (MemoryModel::Classic.ram_size() - 1) as u16;
+        assert_eq!(
+            chip8.write_ram(last_addr, &[1, 2]),
+            Err(Chip8Error::OutOfBounds { addr: last_addr, len: 2 })
+        );
+    }
+
+    #[test]
+    fn draw_reports_a_higher_vip_cycle_cost_than_a_register_load() {
+        let draw_cost = instruction_cycle_cost(0xD005); // DRW V0, V0, 5
+        let load_cost = instruction_cycle_cost(0x6012); // LD V0, 0x12
+        assert!(draw_cost > load_cost, "{draw_cost} should be greater than {load_cost}");
+    }
+
+    #[test]
+    fn vip_cycle_estimate_accumulates_across_ticks() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x200, &[0x60, 0x12]).unwrap(); // LD V0, 0x12
+        assert_eq!(chip8.vip_cycle_estimate(), 0);
+        chip8.tick();
+        assert_eq!(chip8.vip_cycle_estimate(), instruction_cycle_cost(0x6012) as u64);
+    }
+
+    #[test]
+    fn i_register_accessor_reflects_set_i_register() {
+        let mut chip8 = Chip8::new();
+        chip8.set_i_register(0x321);
+        assert_eq!(chip8.i_register(), 0x321);
+    }
+
+    #[test]
+    fn xo_chip_memory_model_allows_addressing_above_0x0fff() {
+        let mut chip8 = Chip8::with_memory_model(MemoryModel::XoChip);
+        let addr = 0x1500u16;
+        assert!(addr as usize > 0x0FFF);
+
+        chip8.write_ram(addr, &[0xAB]).unwrap();
+        assert_eq!(chip8.read_ram(addr, 1).unwrap(), vec![0xAB]);
+
+        chip8.set_i_register(addr);
+        chip8.set_v_register(0, 0xCD);
+        chip8.execute(0xF055).unwrap(); // LD [I], V0
+        assert_eq!(chip8.read_ram(addr, 1).unwrap(), vec![0xCD]);
+
+        let last_addr = (MemoryModel::XoChip.ram_size() - 1) as u16;
+        chip8.write_ram(last_addr, &[1]).unwrap();
+        assert_eq!(
+            chip8.write_ram(last_addr, &[1, 2]),
+            Err(Chip8Error::OutOfBounds { addr: last_addr, len: 2 })
+        );
+    }
+
+    #[test]
+    fn xo_chip_dxyn_wraps_a_sprite_read_off_the_top_of_ram() {
+        let mut chip8 = Chip8::with_memory_model(MemoryModel::XoChip);
+
+        // Sprite starts two bytes before the end of RAM, so its second row
+        // must wrap around to address 0 instead of overflowing/panicking.
+        let last_addr = (MemoryModel::XoChip.ram_size() - 1) as u16;
+        chip8.write_ram(last_addr, &[0b1111_0000]).unwrap();
+        chip8.write_ram(0, &[0b0000_1111]).unwrap();
+
+        chip8.set_i_register(last_addr);
+        chip8.set_v_register(0, 0);
+        chip8.set_v_register(1, 0);
+        chip8.op_drw(0, 1, 2);
+
+        assert_eq!(&chip8.get_display()[0..8], &[true, true, true, true, false, false, false, false]);
+        assert_eq!(
+            &chip8.get_display()[SCREEN_WIDTH..SCREEN_WIDTH + 8],
+            &[false, false, false, false, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn with_start_addr_loads_and_starts_at_a_non_standard_address() {
+        let mut chip8 = Chip8::with_start_addr(0x600);
+        let rom = [0x00, 0xE0]; // CLS
+        chip8.load(&rom).unwrap();
+
+        assert_eq!(chip8.program_counter, 0x600);
+        assert_eq!(chip8.read_ram(0x600, 2).unwrap(), rom.to_vec());
+        // The fontset placement is unaffected by the custom start address.
+        assert_eq!(chip8.read_ram(0, 1).unwrap(), vec![FONTSET[0]]);
+    }
+
+    #[test]
+    fn load_rejects_a_start_addr_that_would_overwrite_the_font_region() {
+        let mut chip8 = Chip8::with_start_addr(0x000);
+        let rom = [0x00, 0xE0]; // CLS
+        assert_eq!(
+            chip8.load(&rom),
+            Err(Chip8Error::OverwritesFontRegion { start: 0x000, len: 2 })
+        );
+    }
+
+    #[test]
+    fn restart_clears_registers_but_leaves_the_loaded_rom_in_ram() {
+        let rom = [0x60, 0x2A, 0x00, 0xE0]; // LD V0, 0x2A; CLS
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.tick(); // LD V0, 0x2A
+        assert_eq!(chip8.v_registers[0], 0x2A);
+        assert_ne!(chip8.program_counter, START_ADDR);
+
+        chip8.restart();
+
+        assert_eq!(chip8.program_counter, START_ADDR);
+        assert_eq!(chip8.v_registers, [0; V_REGISTERS]);
+        assert_eq!(chip8.read_ram(START_ADDR, rom.len()).unwrap(), rom.to_vec());
+    }
+
+    #[test]
+    fn save_state_round_trips_through_a_file_and_the_display_hash_matches() {
+        let rom = [0x60, 0x2A, 0xA2, 0x22, 0xD0, 0x05]; // LD V0, 0x2A; LD I, 0x222; DRW V0,V0,5
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        for _ in 0..3 {
+            chip8.tick();
+        }
+        let expected_hash = chip8.display_hash();
+
+        let path = std::env::temp_dir().join(format!("chip8_savestate_test_{}.state", std::process::id()));
+        chip8.save_state_to_path(&path).unwrap();
+
+        let mut loaded = Chip8::new();
+        loaded.load_state_from_path(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.display_hash(), expected_hash);
+        assert_eq!(loaded.v_registers, chip8.v_registers);
+        assert_eq!(loaded.i_register, chip8.i_register);
+        assert_eq!(loaded.program_counter, chip8.program_counter);
+    }
+
+    #[test]
+    fn load_state_from_path_rejects_a_file_that_isnt_a_save_state() {
+        let path = std::env::temp_dir()
+            .join(format!("chip8_savestate_test_garbage_{}.state", std::process::id()));
+        std::fs::write(&path, b"not a save state").unwrap();
+
+        let mut chip8 = Chip8::new();
+        let result = chip8.load_state_from_path(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn replay_round_trips_the_seed_and_input_log_through_a_file() {
+        let rom = [0x00, 0xE0, 0x12, 0x00]; // CLS, JP 0x200 (back to the CLS)
+        let mut chip8 = Chip8::with_seed(42);
+        chip8.load(&rom).unwrap();
+        chip8.record_input(true);
+        chip8.keypress(0x1, true);
+        chip8.tick();
+        chip8.keypress(0x1, false);
+
+        let path = std::env::temp_dir().join(format!("chip8_replay_test_{}.replay", std::process::id()));
+        chip8.save_replay_to_path(&rom, &path).unwrap();
+
+        let replay = Chip8::load_replay_from_path(&rom, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replay.seed, 42);
+        assert_eq!(replay.input_log, chip8.input_log());
+    }
+
+    #[test]
+    fn load_replay_from_path_rejects_a_mismatched_rom() {
+        let rom = [0x00, 0xE0, 0x12, 0x00]; // CLS, JP 0x200 (back to the CLS)
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.record_input(true);
+        chip8.keypress(0x1, true);
+
+        let path =
+            std::env::temp_dir().join(format!("chip8_replay_test_mismatch_{}.replay", std::process::id()));
+        chip8.save_replay_to_path(&rom, &path).unwrap();
+
+        let different_rom = [0x00, 0xE0, 0x13, 0x00];
+        let result = Chip8::load_replay_from_path(&different_rom, &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err());
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn opcode_at_reads_a_known_opcode_without_advancing_pc() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[0xA1, 0x23]).unwrap(); // LD I, 0x123
+        assert_eq!(chip8.opcode_at(0x300).unwrap(), 0xA123);
+        assert_eq!(chip8.program_counter, START_ADDR);
+
+        let last_addr = (MemoryModel::Classic.ram_size() - 1) as u16;
+        assert_eq!(
+            chip8.opcode_at(last_addr),
+            Err(Chip8Error::OutOfBounds { addr: last_addr, len: 2 })
+        );
+    }
+
+    #[test]
+    fn fx0a_key_order_quirk_picks_between_lowest_index_and_most_recent() {
+        let mut lowest = Chip8::new();
+        lowest.keypress(5, true);
+        lowest.keypress(2, true);
+        lowest.execute(0xF00A).unwrap(); // LD V0, K
+        assert_eq!(lowest.v_registers[0], 2);
+
+        let mut most_recent = Chip8::with_quirks(Quirks {
+            fx0a_key_order: Fx0aKeyOrder::MostRecent,
+            ..Quirks::default()
+        });
+        most_recent.keypress(5, true);
+        most_recent.keypress(2, true);
+        most_recent.execute(0xF00A).unwrap();
+        assert_eq!(most_recent.v_registers[0], 2);
+
+        // Releasing the most recent key falls back to the next-most-recent held key.
+        most_recent.keypress(2, false);
+        most_recent.execute(0xF00A).unwrap();
+        assert_eq!(most_recent.v_registers[0], 5);
+    }
+
+    #[test]
+    fn injected_input_source_drives_the_skip_opcodes_instead_of_key_states() {
+        #[derive(Debug)]
+        struct FixedPattern;
+        impl InputSource for FixedPattern {
+            fn is_pressed(&self, key: usize) -> bool {
+                key == 0x3
+            }
+        }
+
+        let mut chip8 = Chip8::new();
+        chip8.set_input_source(Some(Box::new(FixedPattern)));
+
+        // key_states says nothing is pressed, but the injected source says 0x3 is.
+        chip8.set_v_register(0, 0x3);
+        let pc_before = chip8.program_counter;
+        chip8.execute(0xE09E).unwrap(); // SKP V0
+        assert_eq!(chip8.program_counter, pc_before + 2);
+
+        chip8.set_v_register(0, 0x4);
+        let pc_before = chip8.program_counter;
+        chip8.execute(0xE0A1).unwrap(); // SKNP V0
+        assert_eq!(chip8.program_counter, pc_before + 2);
+
+        chip8.set_input_source(None);
+        let pc_before = chip8.program_counter;
+        chip8.set_v_register(0, 0x3);
+        chip8.execute(0xE09E).unwrap(); // SKP V0 - back to key_states, nothing pressed
+        assert_eq!(chip8.program_counter, pc_before);
+    }
+
+    #[test]
+    fn injected_random_source_drives_cxkk_instead_of_the_seeded_rng() {
+        #[derive(Debug)]
+        struct FixedSequence {
+            values: Vec<u8>,
+            index: usize,
+        }
+        impl RandomSource for FixedSequence {
+            fn next_byte(&mut self) -> u8 {
+                let value = self.values[self.index % self.values.len()];
+                self.index += 1;
+                value
+            }
+        }
+
+        let mut chip8 = Chip8::new();
+        chip8.set_random_source(Some(Box::new(FixedSequence { values: vec![0xFF, 0x0F, 0x00], index: 0 })));
+
+        chip8.execute(0xC0FF).unwrap(); // RND V0, 0xFF
+        assert_eq!(chip8.v_registers[0], 0xFF);
+        chip8.execute(0xC1FF).unwrap(); // RND V1, 0xFF
+        assert_eq!(chip8.v_registers[1], 0x0F);
+        chip8.execute(0xC2FF).unwrap(); // RND V2, 0xFF
+        assert_eq!(chip8.v_registers[2], 0x00);
+        chip8.execute(0xC3FF).unwrap(); // RND V3, 0xFF - sequence wraps around
+        assert_eq!(chip8.v_registers[3], 0xFF);
+    }
+
+    #[test]
+    fn setters_allow_isolated_testing_of_8xy4_add_with_carry() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0xFF);
+        chip8.set_v_register(1, 0x02);
+        chip8.execute(0x8014).unwrap(); // ADD V0, V1
+
+        assert_eq!(chip8.v_registers[0], 0x01);
+        assert_eq!(chip8.v_registers[0xF], 1);
+    }
+
+    #[test]
+    fn draw_reports_the_number_of_erased_pixels() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[0b1111_0000]).unwrap();
+        chip8.set_i_register(0x300);
+
+        chip8.execute(0xD001).unwrap(); // first draw: no existing pixels, no collisions
+        assert_eq!(chip8.last_collision_count(), 0);
+
+        chip8.execute(0xD001).unwrap(); // drawing the same sprite again erases all 4 lit pixels
+        assert_eq!(chip8.last_collision_count(), 4);
+    }
+
+    #[test]
+    fn take_pixel_changes_counts_toggles_and_resets() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[0b1111_0000]).unwrap();
+        chip8.set_i_register(0x300);
+
+        chip8.execute(0xD001).unwrap(); // draws 4 new pixels
+        assert_eq!(chip8.take_pixel_changes(), 4);
+        assert_eq!(chip8.take_pixel_changes(), 0); // cleared after taking
+
+        chip8.execute(0xD001).unwrap(); // erases the same 4 pixels
+        chip8.execute(0x00E0).unwrap(); // CLS: nothing left lit, no additional toggles
+        assert_eq!(chip8.take_pixel_changes(), 4);
+    }
+
+    #[test]
+    fn take_screen_dirty_is_set_by_a_draw_and_cleared_after_reading() {
+        let mut chip8 = Chip8::new();
+        assert!(!chip8.take_screen_dirty()); // nothing drawn yet
+
+        chip8.write_ram(0x300, &[0b1111_0000]).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.execute(0xD001).unwrap(); // DRW V0, V0, 1
+
+        assert!(chip8.take_screen_dirty());
+        assert!(!chip8.take_screen_dirty()); // cleared after taking
+    }
+
+    #[test]
+    fn take_cls_event_fires_once_per_cls() {
+        let mut chip8 = Chip8::new();
+        assert!(!chip8.take_cls_event()); // nothing cleared yet
+
+        chip8.execute(0x00E0).unwrap(); // CLS
+
+        assert!(chip8.take_cls_event());
+        assert!(!chip8.take_cls_event()); // cleared after taking
+    }
+
+    #[test]
+    fn schip_collision_quirk_counts_clipped_and_collided_rows() {
+        let mut chip8 = Chip8::with_quirks(Quirks { schip_collision: true, ..Default::default() });
+        let sprite = [0xFFu8; 15];
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, 0); // x
+        chip8.set_v_register(1, (SCREEN_HEIGHT - 4) as u8); // y = 28: rows 4..14 clip off the bottom
+
+        chip8.execute(0xD01F).unwrap(); // DRW V0, V1, 15
+
+        // 4 rows drawn with no pre-existing pixels (0 collided) + 11 clipped rows
+        assert_eq!(chip8.v_registers[0xF], 11);
+    }
+
+    #[test]
+    fn wrap_x_default_wraps_a_sprite_off_the_right_edge() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[0b1111_1111]).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, (SCREEN_WIDTH - 4) as u8); // x
+        chip8.set_v_register(1, 0); // y
+
+        chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+        let display = chip8.get_display();
+        // The rightmost 4 pixels wrap around to columns 0..4.
+        for (x, &lit) in display.iter().enumerate().skip(SCREEN_WIDTH - 4).take(4) {
+            assert!(lit, "expected column {x} to be lit");
+        }
+        for (x, &lit) in display.iter().enumerate().take(4) {
+            assert!(lit, "expected wrapped column {x} to be lit");
+        }
+    }
+
+    #[test]
+    fn draw_row_fast_path_matches_the_per_pixel_reference_across_random_sprites() {
+        let mut chip8 = Chip8::new();
+        let mut reference = [false; SCREEN_WIDTH * SCREEN_HEIGHT];
+
+        for _ in 0..500 {
+            let sprite: u8 = rand::random();
+            chip8.write_ram(0x300, &[sprite]).unwrap();
+            chip8.set_i_register(0x300);
+
+            // Keep x fully on-screen (0..=SCREEN_WIDTH-8) so every draw hits the
+            // fast path rather than the wrap/clip fallback.
+            let x = rand::random::<u8>() % (SCREEN_WIDTH - 8) as u8;
+            let y = rand::random::<u8>() % SCREEN_HEIGHT as u8;
+            chip8.set_v_register(0, x);
+            chip8.set_v_register(1, y);
+
+            chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+            let mut collided = false;
+            for bit in 0..8 {
+                if sprite & (0b1000_0000 >> bit) != 0 {
+                    let idx = (x as usize + bit) + SCREEN_WIDTH * y as usize;
+                    if reference[idx] {
+                        collided = true;
+                    }
+                    reference[idx] ^= true;
+                }
+            }
+
+            assert_eq!(chip8.get_display(), &reference[..]);
+            assert_eq!(chip8.v_registers()[0xF], collided as u8);
+        }
+    }
+
+    #[test]
+    fn wrap_x_disabled_clips_a_sprite_off_the_right_edge() {
+        let mut chip8 = Chip8::with_quirks(Quirks { wrap_x: false, ..Default::default() });
+        chip8.write_ram(0x300, &[0b1111_1111]).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, (SCREEN_WIDTH - 4) as u8); // x
+        chip8.set_v_register(1, 0); // y
+
+        chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+        let display = chip8.get_display();
+        for (x, &lit) in display.iter().enumerate().skip(SCREEN_WIDTH - 4).take(4) {
+            assert!(lit, "expected column {x} to be lit");
+        }
+        // No pixels should have wrapped around to the left edge.
+        for (x, &lit) in display.iter().enumerate().take(4) {
+            assert!(!lit, "expected column {x} to be clipped, not wrapped");
+        }
+    }
+
+    #[test]
+    fn wrap_y_default_wraps_a_sprite_off_the_bottom_edge() {
+        let mut chip8 = Chip8::new();
+        let sprite = [0xFFu8; 4];
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, 0); // x
+        chip8.set_v_register(1, (SCREEN_HEIGHT - 2) as u8); // y
+
+        chip8.execute(0xD014).unwrap(); // DRW V0, V1, 4
+
+        let display = chip8.get_display();
+        // Rows SCREEN_HEIGHT-2 and SCREEN_HEIGHT-1 draw in place, the other two wrap to rows 0..2.
+        assert!(display[SCREEN_WIDTH * (SCREEN_HEIGHT - 2)]);
+        assert!(display[SCREEN_WIDTH * (SCREEN_HEIGHT - 1)]);
+        assert!(display[0]);
+        assert!(display[SCREEN_WIDTH]);
+    }
+
+    #[test]
+    fn wrap_y_disabled_clips_a_sprite_off_the_bottom_edge() {
+        let mut chip8 = Chip8::with_quirks(Quirks { wrap_y: false, ..Default::default() });
+        let sprite = [0xFFu8; 4];
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, 0); // x
+        chip8.set_v_register(1, (SCREEN_HEIGHT - 2) as u8); // y
+
+        chip8.execute(0xD014).unwrap(); // DRW V0, V1, 4
+
+        let display = chip8.get_display();
+        assert!(display[SCREEN_WIDTH * (SCREEN_HEIGHT - 2)]);
+        assert!(display[SCREEN_WIDTH * (SCREEN_HEIGHT - 1)]);
+        // No pixels should have wrapped around to the top edge.
+        assert!(!display[0]);
+        assert!(!display[SCREEN_WIDTH]);
+    }
+
+    #[test]
+    fn fn01_selects_a_bitplane_and_dxyn_draws_into_it_independently() {
+        let mut chip8 = Chip8::new();
+        let sprite = [0xF0u8]; // top nibble of a single row lit
+        chip8.write_ram(0x300, &sprite).unwrap();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, 0); // x
+        chip8.set_v_register(1, 0); // y
+
+        chip8.execute(0xF101).unwrap(); // select plane 1 only
+        chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+        assert!(chip8.display_plane(0)[0]);
+        assert!(!chip8.display_plane(1)[0]);
+
+        chip8.execute(0xF201).unwrap(); // select plane 2 only
+        chip8.set_v_register(0, 8); // draw plane 2's sprite elsewhere so we can tell them apart
+        chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+        assert!(chip8.display_plane(0)[0]);
+        assert!(!chip8.display_plane(0)[8]);
+        assert!(chip8.display_plane(1)[8]);
+        assert!(!chip8.display_plane(1)[0]);
+    }
+
+    #[test]
+    fn fx33_returns_a_clean_error_when_i_is_near_the_end_of_ram() {
+        let mut chip8 = Chip8::new();
+        let last_addr = (MemoryModel::Classic.ram_size() - 1) as u16;
+        chip8.set_i_register(last_addr);
+
+        assert_eq!(
+            chip8.execute(0xF033), // LD B, V0
+            Err(Chip8Error::OutOfBounds { addr: last_addr, len: 3 })
+        );
+        assert_eq!(chip8.take_memory_error(), None); // execute() doesn't set it, only tick() does
+    }
+
+    #[test]
+    fn fx65_leaves_registers_untouched_when_i_is_near_the_end_of_ram() {
+        let mut chip8 = Chip8::new();
+        let last_addr = (MemoryModel::Classic.ram_size() - 2) as u16;
+        chip8.write_ram(last_addr, &[0xF3, 0x65]).unwrap(); // LD V3, [I] - reads V0..V3, doesn't fit
+        chip8.set_program_counter(last_addr);
+        chip8.set_i_register(last_addr);
+        let registers_before = chip8.v_registers;
+
+        chip8.tick();
+
+        assert_eq!(chip8.v_registers, registers_before);
+        assert_eq!(
+            chip8.last_error(),
+            Some(&Chip8Error::OutOfBounds { addr: last_addr, len: 4 })
+        );
+        // last_error() doesn't clear it, unlike take_memory_error()
+        assert_eq!(chip8.last_error(), Some(&Chip8Error::OutOfBounds { addr: last_addr, len: 4 }));
+    }
+
+    #[test]
+    fn tick_records_a_memory_error_instead_of_panicking() {
+        let mut chip8 = Chip8::new();
+        let last_addr = (MemoryModel::Classic.ram_size() - 2) as u16;
+        chip8.write_ram(last_addr, &[0xF0, 0x33]).unwrap(); // LD B, V0
+        chip8.set_program_counter(last_addr);
+        chip8.set_i_register(last_addr);
+
+        chip8.tick();
+
+        assert_eq!(
+            chip8.take_memory_error(),
+            Some(Chip8Error::OutOfBounds { addr: last_addr, len: 3 })
+        );
+        assert_eq!(chip8.take_memory_error(), None); // taking clears it
+    }
+
+    #[test]
+    fn self_modify_detection_fires_when_a_rom_writes_into_its_own_code() {
+        let mut chip8 = Chip8::new();
+        chip8.detect_self_modify(true);
+        // LD I, 0x200; LD [I], V0 - a ROM writing V0 back over its own first instruction.
+        let rom = [0xA2, 0x00, 0xF0, 0x55];
+        chip8.load(&rom).unwrap();
+
+        chip8.tick(); // LD I, 0x200
+        assert_eq!(chip8.take_self_modify_event(), None);
+
+        chip8.tick(); // LD [I], V0 - writes to 0x200, which is below the now-current pc
+        assert_eq!(chip8.take_self_modify_event(), Some((0x200, 0x204)));
+        assert_eq!(chip8.take_self_modify_event(), None); // taking clears it
+    }
+
+    #[test]
+    fn self_modify_detection_is_off_by_default() {
+        let mut chip8 = Chip8::new();
+        let rom = [0xA2, 0x00, 0xF0, 0x55];
+        chip8.load(&rom).unwrap();
+
+        chip8.tick();
+        chip8.tick();
+
+        assert_eq!(chip8.take_self_modify_event(), None);
+    }
+
+    #[test]
+    fn out_of_bounds_jump_detection_fires_when_a_rom_jumps_into_the_font_region() {
+        let mut chip8 = Chip8::new();
+        chip8.detect_out_of_bounds_jump(true);
+        // JP 0x000 - jumps straight into the font region, well below the ROM.
+        let rom = [0x10, 0x00];
+        chip8.load(&rom).unwrap();
+
+        assert_eq!(chip8.take_out_of_bounds_jump_event(), None);
+        chip8.tick();
+        assert_eq!(chip8.take_out_of_bounds_jump_event(), Some(0x000));
+        assert_eq!(chip8.take_out_of_bounds_jump_event(), None); // taking clears it
+    }
+
+    #[test]
+    fn out_of_bounds_jump_detection_does_not_overflow_on_a_rom_filling_all_of_xo_chip_ram() {
+        // start_addr (0x200) + rom_len fills RAM exactly, so program_end
+        // (program_start + rom_len) lands on 65536 - one past what a u16 can hold.
+        let mut chip8 = Chip8::with_memory_model(MemoryModel::XoChip);
+        chip8.detect_out_of_bounds_jump(true);
+        let rom = vec![0u8; MemoryModel::XoChip.ram_size() - START_ADDR as usize]; // all NOPs
+        chip8.load(&rom).unwrap();
+
+        chip8.tick(); // must not panic with "attempt to add with overflow"
+        assert_eq!(chip8.take_out_of_bounds_jump_event(), None);
+    }
+
+    #[test]
+    fn out_of_bounds_jump_detection_is_off_by_default() {
+        let mut chip8 = Chip8::new();
+        let rom = [0x10, 0x00]; // JP 0x000
+        chip8.load(&rom).unwrap();
+
+        chip8.tick();
+
+        assert_eq!(chip8.take_out_of_bounds_jump_event(), None);
+    }
+
+    #[test]
+    fn is_waiting_for_key_toggles_around_fx0a() {
+        let rom = assemble("LD V0, K").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        chip8.tick();
+        assert!(chip8.is_waiting_for_key());
+
+        chip8.keypress(0x5, true);
+        chip8.tick();
+        assert!(!chip8.is_waiting_for_key());
+        assert_eq!(chip8.v_registers[0], 0x5);
+    }
+
+    #[test]
+    fn fx0a_re_fetches_the_same_instruction_until_a_key_is_pressed() {
+        let rom = assemble("LD V0, K").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        let start_pc = chip8.program_counter;
+
+        for _ in 0..3 {
+            chip8.tick();
+            assert!(chip8.is_waiting_for_key());
+            assert_eq!(chip8.program_counter, start_pc);
+        }
+
+        chip8.keypress(0x5, true);
+        chip8.tick();
+        assert!(!chip8.is_waiting_for_key());
+        assert_eq!(chip8.program_counter, start_pc + 2);
+        assert_eq!(chip8.v_registers[0], 0x5);
+    }
+
+    #[test]
+    fn opcode_coverage_tracks_instruction_categories() {
+        let rom = assemble("LD V0, 1\nLD V1, 2\nADD V0, V1\nADD V0, V1").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        for _ in 0..4 {
+            chip8.tick();
+        }
+
+        let coverage = chip8.opcode_coverage();
+        assert_eq!(coverage.get(&0x6000), Some(&2)); // two LD Vx, byte
+        assert_eq!(coverage.get(&0x8004), Some(&2)); // two ADD Vx, Vy
+    }
+
+    #[test]
+    fn register_write_log_is_off_by_default_and_records_every_write_when_enabled() {
+        let rom = assemble("LD V0, 1\nLD V1, 2\nADD V0, V1\nLD I, 0x300").unwrap();
+
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        for _ in 0..4 {
+            chip8.tick();
+        }
+        assert!(chip8.register_write_log().is_empty());
+
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.record_register_writes(true);
+        for _ in 0..4 {
+            chip8.tick();
+        }
+
+        assert_eq!(
+            chip8.register_write_log(),
+            &[
+                (0, RegisterWrite::V { index: 0, old: 0, new: 1 }),
+                (1, RegisterWrite::V { index: 1, old: 0, new: 2 }),
+                (2, RegisterWrite::V { index: 0, old: 1, new: 3 }),
+                (2, RegisterWrite::V { index: 0xF, old: 0, new: 0 }),
+                (3, RegisterWrite::I { old: 0, new: 0x300 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn branch_log_is_off_by_default_and_records_skip_decisions_when_enabled() {
+        let rom = assemble("LD V0, 1\nSE V0, 1\nLD V1, 2\nSNE V0, 2\nLD V2, 3").unwrap();
+
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        for _ in 0..5 {
+            chip8.tick();
+        }
+        assert!(chip8.branch_log().is_empty());
+
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.record_branch_decisions(true);
+        for _ in 0..5 {
+            chip8.tick();
+        }
+
+        assert_eq!(
+            chip8.branch_log(),
+            &[
+                BranchDecision { pc: 0x202, opcode: 0x3001, skipped: true },
+                BranchDecision { pc: 0x206, opcode: 0x4002, skipped: true },
+            ]
+        );
+    }
+
+    #[test]
+    fn logic_reset_vf_quirk_clears_vf_after_or() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 1);
+        chip8.execute(0x8011).unwrap(); // OR V0, V1
+        assert_eq!(chip8.v_registers[0xF], 1); // untouched by default
+
+        let mut chip8 = Chip8::with_quirks(Quirks { logic_reset_vf: true, ..Default::default() });
+        chip8.set_v_register(0xF, 1);
+        chip8.execute(0x8011).unwrap(); // OR V0, V1
+        assert_eq!(chip8.v_registers[0xF], 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sys_opcode_panics_by_default() {
+        let mut chip8 = Chip8::new();
+        chip8.execute(0x0123).unwrap();
+    }
+
+    #[test]
+    fn program_counter_wraps_instead_of_running_off_the_end_of_ram() {
+        let mut chip8 = Chip8::new();
+        let ram_size = MemoryModel::Classic.ram_size() as u16;
+        chip8.write_ram(ram_size - 2, &[0x00, 0x00]).unwrap(); // NOP at the last address
+        chip8.set_program_counter(ram_size - 2);
+
+        chip8.tick(); // must not panic reading/incrementing past the end of RAM
+
+        assert_eq!(chip8.program_counter, 0);
+    }
+
+    #[test]
+    fn jumping_to_an_odd_address_is_reported_as_misaligned() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x301, &[0xA1, 0x23]).unwrap(); // LD I, 0x123 at an odd address
+        assert!(chip8.is_aligned());
+
+        chip8.execute(0x1301).unwrap(); // JP 0x301
+        assert!(!chip8.is_aligned());
+
+        chip8.tick();
+        assert_eq!(chip8.i_register(), 0x123);
+    }
+
+    #[test]
+    fn sys_opcode_is_a_noop_under_the_quirk() {
+        let mut chip8 = Chip8::with_quirks(Quirks { allow_sys_noop: true, ..Default::default() });
+        let pc_before = chip8.program_counter;
+        chip8.execute(0x0123).unwrap();
+        assert_eq!(chip8.program_counter, pc_before);
+    }
+
+    #[test]
+    fn undefined_5xy_and_9xy_forms_are_silent_no_ops_by_default() {
+        let mut chip8 = Chip8::new();
+        let pc_before = chip8.program_counter;
+        chip8.execute(0x5001).unwrap(); // 5xy1, undefined
+        chip8.execute(0x9001).unwrap(); // 9xy1, undefined
+        assert_eq!(chip8.program_counter, pc_before);
+    }
+
+    #[test]
+    fn undefined_5xy_and_9xy_forms_error_under_the_strict_quirk() {
+        let mut chip8 = Chip8::with_quirks(Quirks {
+            strict_undefined_forms: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            chip8.execute(0x5001),
+            Err(Chip8Error::UnknownOpcode { opcode: 0x5001 })
+        );
+        assert_eq!(
+            chip8.execute(0x9001),
+            Err(Chip8Error::UnknownOpcode { opcode: 0x9001 })
+        );
+    }
+
+    #[test]
+    fn xo_chip_5xy2_and_5xy3_store_and_load_an_arbitrary_register_range() {
+        let mut chip8 = Chip8::new();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(2, 0x11);
+        chip8.set_v_register(3, 0x22);
+        chip8.set_v_register(4, 0x33);
+        chip8.set_v_register(5, 0x44);
+
+        chip8.execute(0x5252).unwrap(); // 5xy2: store V2..V5 at I
+        assert_eq!(chip8.read_ram(0x300, 4).unwrap(), vec![0x11, 0x22, 0x33, 0x44]);
+        assert_eq!(chip8.i_register(), 0x300); // I is left unchanged
+
+        chip8.execute(0x5863).unwrap(); // 5xy3: load I.. into V8..V6 (backward)
+        assert_eq!(chip8.v_registers[8], 0x11);
+        assert_eq!(chip8.v_registers[7], 0x22);
+        assert_eq!(chip8.v_registers[6], 0x33);
+    }
+
+    #[test]
+    fn run_until_timer_tick_respects_max_cycles_and_decrements_once() {
+        let mut chip8 = Chip8::new();
+        chip8.delay_timer_register = 5;
+
+        let ran = chip8.run_until_timer_tick(3);
+        assert_eq!(ran, 3);
+        assert_eq!(chip8.delay_timer_register, 4);
+
+        let ran = chip8.run_until_timer_tick(100);
+        assert_eq!(ran, TIMER_TICK_INSTRUCTION_BUDGET);
+        assert_eq!(chip8.delay_timer_register, 3);
+    }
+
+    #[test]
+    fn run_to_stops_at_the_target_address() {
+        let rom = assemble("
+            LD V0, 1
+            LD V1, 2
+            LD V2, 3
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        let result = chip8.run_to(0x204, 100);
+
+        assert_eq!(result, RunToResult::Reached);
+        assert_eq!(chip8.program_counter, 0x204);
+        assert_eq!(chip8.v_registers[0], 1);
+        assert_eq!(chip8.v_registers[1], 2);
+        assert_eq!(chip8.v_registers[2], 0); // not yet executed
+    }
+
+    #[test]
+    fn run_with_limit_halts_a_jump_to_self_rom_at_the_cycle_limit() {
+        let rom = assemble("start: JP start").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        let result = chip8.run_with_limit(1000);
+
+        assert_eq!(result, Ok(TickOutcome::LimitReached));
+    }
+
+    #[test]
+    fn step_frame_runs_exactly_ipf_ticks_and_decrements_the_timer_once() {
+        let rom = assemble("
+            LD V0, 60
+            LD DT, V0
+        loop:
+            LD V1, 1
+            JP loop
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.tick(); // LD V0, 60
+        chip8.tick(); // LD DT, V0 - arms the timer before step_frame runs the loop
+
+        let result = chip8.step_frame(5);
+
+        assert_eq!(result, Ok(TickOutcome::Normal));
+        assert_eq!(chip8.program_counter, 0x206); // 5 ticks of the 2-instruction loop land mid-loop
+        assert_eq!(chip8.delay_timer_register, 59); // exactly one timer decrement
+    }
+
+    #[test]
+    fn opcode_00fd_halts_the_interpreter_without_panicking() {
+        let rom = assemble("DB 0x00, 0xFD\nJP start\nstart:").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        assert!(!chip8.is_halted());
+        let result = chip8.run_with_limit(1000);
+
+        assert_eq!(result, Ok(TickOutcome::Halted));
+        assert!(chip8.is_halted());
+    }
+
+    #[test]
+    fn opcodes_00ff_and_00fe_toggle_hi_res_mode() {
+        let mut chip8 = Chip8::new();
+        assert!(!chip8.is_hi_res());
+
+        chip8.execute(0x00FF).unwrap(); // HIGH
+        assert!(chip8.is_hi_res());
+
+        chip8.execute(0x00FE).unwrap(); // LOW
+        assert!(!chip8.is_hi_res());
+    }
+
+    #[test]
+    fn hi_res_mode_resizes_the_display_buffer_and_can_draw_past_the_low_res_edge() {
+        let mut chip8 = Chip8::new();
+        assert_eq!(chip8.get_display().len(), SCREEN_WIDTH * SCREEN_HEIGHT);
+
+        chip8.execute(0x00FF).unwrap(); // HIGH
+        assert_eq!(chip8.display_width(), HI_RES_WIDTH);
+        assert_eq!(chip8.display_height(), HI_RES_HEIGHT);
+        assert_eq!(chip8.get_display().len(), HI_RES_WIDTH * HI_RES_HEIGHT);
+
+        // A sprite drawn at x=100 only fits on-screen in hi-res mode - in low-res
+        // mode (width 64) it would be entirely off the right edge.
+        chip8.i_register = 0x300;
+        chip8.ram[0x300] = 0b1111_0000;
+        chip8.v_registers[0] = 100;
+        chip8.v_registers[1] = 60;
+        chip8.execute(0xD011).unwrap(); // DRW V0, V1, 1
+
+        let display = chip8.get_display();
+        assert!(display[60 * HI_RES_WIDTH + 100]);
+        assert!(!display[60 * HI_RES_WIDTH + 104]);
+
+        chip8.execute(0x00FE).unwrap(); // LOW
+        assert_eq!(chip8.get_display().len(), SCREEN_WIDTH * SCREEN_HEIGHT);
+    }
+
+    #[test]
+    fn tick_reports_normal_breakpoint_and_halted_outcomes() {
+        let rom = assemble("
+            LD V0, 1
+            LD V1, 2
+            DB 0x00, 0xFD
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        chip8.add_breakpoint(0x202);
+
+        assert_eq!(chip8.tick(), TickOutcome::Normal);
+        assert_eq!(chip8.tick(), TickOutcome::Breakpoint(0x202));
+        assert_eq!(chip8.tick(), TickOutcome::Halted); // 00FD at 0x204 halts the interpreter
+        assert_eq!(chip8.tick(), TickOutcome::Halted); // stays halted on further ticks
+    }
+
+    #[test]
+    fn dump_ram_range_formats_a_known_region_xxd_style() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, b"Hello, World!!!!").unwrap();
+
+        let dump = chip8.dump_ram_range(0x300, 16);
+
+        assert_eq!(
+            dump,
+            "0300: 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64 21 21 21 21 |Hello, World!!!!|\n"
+        );
+    }
+
+    #[test]
+    fn dump_ram_range_pads_a_short_trailing_line() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[0xDE, 0xAD]).unwrap();
+
+        let dump = chip8.dump_ram_range(0x300, 2);
+
+        assert_eq!(dump, "0300: de ad                                           |..|\n");
+    }
+
+    #[test]
+    fn extract_sprite_and_sprite_to_string_render_the_digit_0_glyph() {
+        let chip8 = Chip8::new();
+
+        let sprite = chip8.extract_sprite(0x000, 5).unwrap();
+
+        assert_eq!(sprite, vec![0xF0, 0x90, 0x90, 0x90, 0xF0]);
+        assert_eq!(
+            Chip8::sprite_to_string(&sprite),
+            "####    \n\
+             #  #    \n\
+             #  #    \n\
+             #  #    \n\
+             ####    "
+        );
+    }
+
+    #[test]
+    fn stack_depth_and_call_stack_track_nested_calls() {
+        let rom = assemble("
+            start:
+                CALL a
+            a:
+                CALL b
+            b:
+                JP b
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        chip8.tick(); // CALL a
+        chip8.tick(); // CALL b
+
+        assert_eq!(chip8.stack_depth(), 2);
+        assert_eq!(chip8.call_stack(), &[0x202, 0x204]);
+    }
+
+    #[test]
+    fn stack_overflow_behavior_ignore_skips_call_past_the_stack_limit() {
+        let mut chip8 = Chip8::with_quirks(Quirks {
+            stack_overflow_behavior: StackOverflowBehavior::Ignore,
+            ..Default::default()
+        });
+        for _ in 0..16 {
+            chip8.execute(0x2300).unwrap(); // CALL 0x300 - fills the stack to capacity
+        }
+        assert_eq!(chip8.stack_depth(), 16);
+
+        chip8.execute(0x2400).unwrap(); // 17th CALL: stack already full, silently skipped
+        assert_eq!(chip8.stack_depth(), 16); // unchanged
+        assert_eq!(chip8.program_counter, 0x300); // didn't jump to 0x400 either
+    }
+
+    #[test]
+    fn call_frames_infers_the_call_site_of_each_nested_call() {
+        let rom = assemble(
+            "
+            start:
+                CALL a
+            a:
+                CALL b
+            b:
+                JP b
+            ",
+        )
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        chip8.tick(); // CALL a
+        chip8.tick(); // CALL b
+
+        assert_eq!(
+            chip8.call_frames(),
+            vec![
+                CallFrame { return_addr: 0x202, call_site: 0x200 },
+                CallFrame { return_addr: 0x204, call_site: 0x202 },
+            ]
+        );
+    }
+
+    #[test]
+    fn memory_regions_bounds_the_program_area_to_the_loaded_rom_length() {
+        let mut chip8 = Chip8::new();
+        chip8.load(&[0x12, 0x00, 0x00, 0x00, 0x00]).unwrap(); // 5-byte ROM
+
+        assert_eq!(
+            chip8.memory_regions(),
+            vec![
+                MemoryRegion { kind: MemoryRegionKind::Font, start: 0, end: 80 },
+                MemoryRegion { kind: MemoryRegionKind::Free, start: 80, end: 0x200 },
+                MemoryRegion { kind: MemoryRegionKind::Program, start: 0x200, end: 0x205 },
+                MemoryRegion { kind: MemoryRegionKind::Free, start: 0x205, end: MemoryModel::Classic.ram_size() },
+            ]
+        );
+    }
+
+    #[test]
+    fn step_to_next_draw_skips_the_loop_and_lands_after_the_dxyn() {
+        let rom = assemble("
+            LD V0, 5
+        delay:
+            ADD V0, 255
+            SE V0, 0
+            JP delay
+            DRW V0, V0, 1
+            LD V1, 42
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        assert!(chip8.step_to_next_draw(1000));
+
+        // PC should land right after the DRW, with the delay loop already spent.
+        assert_eq!(chip8.v_registers[0], 0);
+        assert_eq!(chip8.v_registers[1], 0); // not yet executed
+        chip8.tick();
+        assert_eq!(chip8.v_registers[1], 42);
+    }
+
+    #[test]
+    fn step_to_next_draw_returns_false_when_the_cycle_cap_is_hit_first() {
+        let rom = assemble("
+        delay:
+            JP delay
+        ")
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        assert!(!chip8.step_to_next_draw(50));
+    }
+
+    #[test]
+    fn step_over_runs_through_a_call_and_lands_after_it() {
+        let rom = assemble(
+            "
+            CALL sub
+            LD V0, 42
+            JP self
+            self:
+                JP self
+            sub:
+                LD V1, 1
+                LD V2, 2
+                RET
+            ",
+        )
+        .unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+        let after_call = START_ADDR + 2;
+
+        chip8.step_over(1000).unwrap();
+
+        assert_eq!(chip8.program_counter, after_call);
+        assert_eq!(chip8.stack_depth(), 0);
+        // The whole subroutine ran, not just the CALL itself.
+        assert_eq!(chip8.v_registers[1], 1);
+        assert_eq!(chip8.v_registers[2], 2);
+    }
+
+    #[test]
+    fn step_over_behaves_like_a_single_tick_when_not_a_call() {
+        let rom = assemble("LD V0, 42").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        chip8.step_over(1000).unwrap();
+
+        assert_eq!(chip8.v_registers[0], 42);
+        assert_eq!(chip8.program_counter, START_ADDR + 2);
+    }
+
+    #[test]
+    fn run_to_reports_the_cycle_limit_when_the_address_is_never_reached() {
+        let rom = assemble("LD V0, 1").unwrap();
+        let mut chip8 = Chip8::new();
+        chip8.load(&rom).unwrap();
+
+        let result = chip8.run_to(0xFFF, 3);
+
+        assert_eq!(result, RunToResult::CycleLimitReached);
+    }
+
+    #[test]
+    fn identical_runs_produce_identical_snapshots() {
+        let rom = assemble("LD V0, 1\nLD V1, 2\nADD V0, V1").unwrap();
+
+        let mut a = Chip8::new();
+        a.load(&rom).unwrap();
+        let mut b = Chip8::new();
+        b.load(&rom).unwrap();
+        for _ in 0..3 {
+            a.tick();
+            b.tick();
+        }
+
+        assert_eq!(a.snapshot(), b.snapshot());
+    }
+
+    #[test]
+    fn set_keys_replaces_the_whole_keypad() {
+        let mut chip8 = Chip8::new();
+        let mut states = [false; NUM_KEYS];
+        states[0x3] = true;
+        states[0xA] = true;
+        chip8.set_keys(states);
+
+        for i in 0..NUM_KEYS {
+            assert_eq!(chip8.is_key_pressed(i), i == 0x3 || i == 0xA);
+        }
+    }
+
+    #[test]
+    fn key_mask_round_trips_through_encode_and_apply() {
+        let mut sender = Chip8::new();
+        let mut states = [false; NUM_KEYS];
+        states[0x3] = true;
+        states[0xA] = true;
+        sender.set_keys(states);
+
+        let mask = sender.encode_keys();
+        assert_eq!(mask, 0b0000_0100_0000_1000);
+
+        let mut receiver = Chip8::new();
+        receiver.apply_key_mask(mask);
+        for i in 0..NUM_KEYS {
+            assert_eq!(receiver.is_key_pressed(i), i == 0x3 || i == 0xA);
+        }
+    }
+
+    #[test]
+    fn recorded_input_replays_to_the_same_final_display() {
+        let rom = assemble("loop: LD V0, K\nDRW V0, V0, 1\nJP loop").unwrap();
+
+        let mut recorder = Chip8::new();
+        recorder.load(&rom).unwrap();
+        recorder.record_input(true);
+        recorder.tick(); // blocks on Fx0A
+        recorder.keypress(0x1, true);
+        recorder.tick(); // resolves Fx0A, ticks fetch DRW
+        recorder.tick(); // DRW
+        recorder.tick(); // JP loop
+
+        let log = recorder.input_log().to_vec();
+
+        let mut player = Chip8::new();
+        player.load(&rom).unwrap();
+        player.replay_input(&log);
+        // Run the same number of extra ticks the recorder ran after its last input.
+        player.tick();
+        player.tick();
+
+        assert_eq!(recorder.snapshot().packed_display, player.snapshot().packed_display);
+    }
+
+    #[test]
+    fn eight_xy_family_writes_vf_last_when_x_is_vf() {
+        // 8xy4: ADD VF, Vy with carry - the flag must land the carry, not the sum.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 0xFF);
+        chip8.set_v_register(0x0, 0x02);
+        chip8.execute(0x8F04).unwrap(); // ADD VF, V0
+        assert_eq!(chip8.v_registers[0xF], 1);
+
+        // 8xy5: SUB VF, Vy with no borrow - the flag must land 1, not the difference.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 0x05);
+        chip8.set_v_register(0x0, 0x02);
+        chip8.execute(0x8F05).unwrap(); // SUB VF, V0
+        assert_eq!(chip8.v_registers[0xF], 1);
+
+        // 8xy7: SUBN VF, Vy - the flag must land the not-borrow bit, not the result.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 0x02);
+        chip8.set_v_register(0x0, 0x05);
+        chip8.execute(0x8F07).unwrap(); // SUBN VF, V0
+        assert_eq!(chip8.v_registers[0xF], 1);
+
+        // 8xy6: SHR VF - the flag must land the shifted-out bit, not the shifted value.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 0x03);
+        chip8.execute(0x8F06).unwrap(); // SHR VF
+        assert_eq!(chip8.v_registers[0xF], 1);
+
+        // 8xyE: SHL VF - the flag must land the shifted-out bit, not the shifted value.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0xF, 0b1000_0001);
+        chip8.execute(0x8F0E).unwrap(); // SHL VF
+        assert_eq!(chip8.v_registers[0xF], 1);
+    }
+
+    #[test]
+    fn flag_first_quirk_lets_8xy4s_result_clobber_vf_when_x_is_vf() {
+        // 8xy4: ADD VF, Vy with carry - under FlagFirst, the sum overwrites the
+        // flag this same instruction just wrote, instead of the flag winning.
+        let mut chip8 = Chip8::with_quirks(Quirks {
+            flag_write_order: FlagWriteOrder::FlagFirst,
+            ..Default::default()
+        });
+        chip8.set_v_register(0xF, 0xFF);
+        chip8.set_v_register(0x0, 0x02);
+        chip8.execute(0x8F04).unwrap(); // ADD VF, V0
+        assert_eq!(chip8.v_registers[0xF], 1); // 0xFF + 2 wraps to 1, and 1 overwrote the carry flag
+    }
+
+    #[test]
+    fn shift_quirk_selects_which_register_is_shifted_and_which_bit_flags_vf() {
+        // UseVx (default): Vx is shifted in place, Vy is irrelevant.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0b0000_0011); // Vx, lsb set
+        chip8.set_v_register(1, 0b1000_0000); // Vy, msb set
+        chip8.execute(0x8016).unwrap(); // SHR V0, V1
+        assert_eq!(chip8.v_registers[0], 0b0000_0001);
+        assert_eq!(chip8.v_registers[0xF], 1); // Vx's lsb, not Vy's
+
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0b0000_0011);
+        chip8.set_v_register(1, 0b1000_0000);
+        chip8.execute(0x801E).unwrap(); // SHL V0, V1
+        assert_eq!(chip8.v_registers[0], 0b0000_0110);
+        assert_eq!(chip8.v_registers[0xF], 0); // Vx's msb, not Vy's
+
+        // UseVy: Vy is shifted and stored into Vx, and VF gets Vy's shifted-out bit.
+        let mut chip8 = Chip8::with_quirks(Quirks { shift_quirk: ShiftQuirk::UseVy, ..Default::default() });
+        chip8.set_v_register(0, 0b0000_0011);
+        chip8.set_v_register(1, 0b1000_0000);
+        chip8.execute(0x8016).unwrap(); // SHR V0, V1
+        assert_eq!(chip8.v_registers[0], 0b0100_0000);
+        assert_eq!(chip8.v_registers[0xF], 0); // Vy's lsb, not Vx's
+
+        let mut chip8 = Chip8::with_quirks(Quirks { shift_quirk: ShiftQuirk::UseVy, ..Default::default() });
+        chip8.set_v_register(0, 0b0000_0011);
+        chip8.set_v_register(1, 0b1000_0000);
+        chip8.execute(0x801E).unwrap(); // SHL V0, V1
+        assert_eq!(chip8.v_registers[0], 0b0000_0000);
+        assert_eq!(chip8.v_registers[0xF], 1); // Vy's msb, not Vx's
+    }
+
+    #[test]
+    fn shl_8xye_takes_its_result_and_flag_from_the_correct_register_under_each_shift_quirk() {
+        // UseVx (default): Vx's own msb shifts out into VF, Vy is irrelevant.
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0b0100_0001); // Vx
+        chip8.set_v_register(1, 0b1000_0000); // Vy
+        chip8.execute(0x801E).unwrap(); // SHL V0, V1
+        assert_eq!(chip8.v_registers[0], 0b1000_0010);
+        assert_eq!(chip8.v_registers[0xF], 0); // Vx's msb, not Vy's
+
+        // UseVy: Vy is shifted and stored into Vx, and VF gets Vy's msb instead.
+        let mut chip8 = Chip8::with_quirks(Quirks { shift_quirk: ShiftQuirk::UseVy, ..Default::default() });
+        chip8.set_v_register(0, 0b0100_0001);
+        chip8.set_v_register(1, 0b1000_0000);
+        chip8.execute(0x801E).unwrap(); // SHL V0, V1
+        assert_eq!(chip8.v_registers[0], 0b0000_0000);
+        assert_eq!(chip8.v_registers[0xF], 1); // Vy's msb, not Vx's
+    }
+
+    #[test]
+    fn fx65_only_modifies_v0_through_vx_and_leaves_the_rest_untouched() {
+        let mut chip8 = Chip8::new();
+        chip8.write_ram(0x300, &[1, 2, 3, 4]).unwrap();
+        chip8.set_i_register(0x300);
+        for reg in 4..=0xF {
+            chip8.set_v_register(reg, 0xAA);
+        }
+
+        chip8.execute(0xF365).unwrap(); // LD V3, [I]
+
+        assert_eq!(&chip8.v_registers[0..=3], &[1, 2, 3, 4]);
+        for reg in 4..=0xF {
+            assert_eq!(chip8.v_registers[reg], 0xAA, "V{reg:X} should be untouched");
+        }
+    }
+
+    #[test]
+    fn load_store_quirk_controls_whether_fx55_and_fx65_advance_i() {
+        // Unchanged (default): I is left pointing at the same address.
+        let mut chip8 = Chip8::new();
+        chip8.set_i_register(0x300);
+        chip8.execute(0xF355).unwrap(); // LD [I], V3
+        assert_eq!(chip8.i_register(), 0x300);
+
+        chip8.set_i_register(0x300);
+        chip8.execute(0xF365).unwrap(); // LD V3, [I]
+        assert_eq!(chip8.i_register(), 0x300);
+
+        // Increment: I advances by x + 1, past the last address touched.
+        let mut chip8 =
+            Chip8::with_quirks(Quirks { load_store_quirk: LoadStoreQuirk::Increment, ..Default::default() });
+        chip8.set_i_register(0x300);
+        chip8.execute(0xF355).unwrap(); // LD [I], V3
+        assert_eq!(chip8.i_register(), 0x304);
+
+        chip8.set_i_register(0x300);
+        chip8.execute(0xF365).unwrap(); // LD V3, [I]
+        assert_eq!(chip8.i_register(), 0x304);
+    }
+
+    #[test]
+    fn load_store_quirk_increment_wraps_instead_of_overflowing_at_the_top_of_xo_chip_ram() {
+        let mut chip8 = Chip8::with_memory_model(MemoryModel::XoChip);
+        chip8.quirks.load_store_quirk = LoadStoreQuirk::Increment;
+        chip8.set_i_register(u16::MAX - 15);
+        chip8.execute(0xFF55).unwrap(); // LD [I], VF
+        assert_eq!(chip8.i_register(), 0);
+
+        chip8.set_i_register(u16::MAX - 15);
+        chip8.execute(0xFF65).unwrap(); // LD VF, [I]
+        assert_eq!(chip8.i_register(), 0);
+    }
+
+    #[test]
+    fn op_add_vx_vy_sets_vf_on_overflow_without_going_through_execute() {
+        let mut chip8 = Chip8::new();
+        chip8.set_v_register(0, 0xFF);
+        chip8.set_v_register(1, 2);
+        chip8.op_add_vx_vy(0, 1);
+        assert_eq!(chip8.v_registers[0], 1);
+        assert_eq!(chip8.v_registers[0xF], 1);
+    }
+
+    #[test]
+    fn op_jp_sets_the_program_counter_directly() {
+        let mut chip8 = Chip8::new();
+        chip8.op_jp(0x300);
+        assert_eq!(chip8.program_counter, 0x300);
+    }
+
+    #[test]
+    fn op_ld_b_vx_writes_the_bcd_digits_directly() {
+        let mut chip8 = Chip8::new();
+        chip8.set_i_register(0x300);
+        chip8.set_v_register(0, 246);
+        chip8.op_ld_b_vx(0).unwrap();
+        assert_eq!(chip8.read_ram(0x300, 3).unwrap(), [2, 4, 6]);
     }
 }
@@ -0,0 +1,155 @@
+//! Platform presets used to guess which behavioral quirks a ROM expects.
+
+/// The most advanced CHIP-8-family platform a ROM appears to target.
+///
+/// Variants are ordered from least to most advanced so the "most advanced
+/// platform found" scan in [`crate::rom::detect_platform`] can just take the max.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QuirksPreset {
+    Classic,
+    SuperChip,
+    XoChip,
+}
+
+/// How much addressable RAM the machine has.
+///
+/// Classic CHIP-8/SCHIP interpreters only had 4K, addressed by the 12-bit
+/// immediates baked into opcodes like `Annn`. XO-CHIP raises this to 64K,
+/// reachable via `I` once it's been loaded with a full 16-bit address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryModel {
+    Classic,
+    XoChip,
+}
+
+impl MemoryModel {
+    pub const fn ram_size(self) -> usize {
+        match self {
+            MemoryModel::Classic => 4096,
+            MemoryModel::XoChip => 65536,
+        }
+    }
+}
+
+/// Which key `Fx0A` stores when multiple keys are held at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fx0aKeyOrder {
+    /// Store the lowest-indexed pressed key. This is the native default.
+    LowestIndex,
+    /// Store whichever held key was pressed most recently.
+    MostRecent,
+}
+
+/// Whether `8xy4`/`8xy5`/`8xy7`/`8xy6`/`8xyE` write the arithmetic result or the
+/// VF flag last. This only matters when the destination register `Vx` (for
+/// `8xy7` too) is VF itself, since VF is a general-purpose register those
+/// opcodes can legally target as well as the flag they always write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagWriteOrder {
+    /// Write VF last, so it always ends up holding the flag even if the
+    /// destination register was VF. This is the native default.
+    FlagLast,
+    /// Write the destination register last, so if it's VF the arithmetic
+    /// result overwrites the flag this same instruction just wrote. Some
+    /// interpreters and strict test ROMs expect this ordering instead.
+    FlagFirst,
+}
+
+/// Which register `8xy6`/`8xyE` (SHR/SHL) shift: the destination `Vx`, or the
+/// source `Vy`, storing the result in `Vx` either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShiftQuirk {
+    /// Shift `Vx` in place, ignoring `Vy` entirely. This is the native default.
+    UseVx,
+    /// Shift `Vy` and store the result in `Vx`, matching the original COSMAC
+    /// VIP interpreter. VF gets the bit shifted out of `Vy`, not `Vx`.
+    UseVy,
+}
+
+/// Whether `Fx55`/`Fx65` leave `I` unchanged after transferring `V0..=Vx`, or
+/// advance it to just past the last address touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStoreQuirk {
+    /// Leave `I` unchanged. This is the native default.
+    Unchanged,
+    /// Advance `I` by `x + 1`, matching the original COSMAC VIP.
+    Increment,
+}
+
+/// What `CALL` does when the call stack is already full (16 nested calls deep).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackOverflowBehavior {
+    /// Raise [`crate::Chip8Error::StackOverflow`]. This is the native default.
+    Error,
+    /// Silently skip the `CALL` (no push, no jump), so a buggy ROM that
+    /// recurses too deep keeps running instead of erroring out.
+    Ignore,
+}
+
+/// Toggles for behavioral differences between CHIP-8 interpreter implementations.
+///
+/// Different ROMs were written against different original interpreters, which
+/// disagree on several edge cases. Each flag defaults to the behavior this
+/// emulator implements natively (matching most modern interpreters); flip it
+/// on to reproduce the quirk a particular ROM expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quirks {
+    /// On the original COSMAC VIP, `8xy1`/`8xy2`/`8xy3` (OR/AND/XOR) reset VF to 0.
+    pub logic_reset_vf: bool,
+    /// Treat `0nnn` (SYS addr, call machine code routine) as a no-op instead of
+    /// an unknown-opcode panic. Some very old ROMs contain a leading `0nnn` call.
+    pub allow_sys_noop: bool,
+    /// On SCHIP, `Dxyn` sets VF to the number of sprite rows that collided or were
+    /// clipped off the bottom edge, instead of a plain 0/1 collision flag. Rows
+    /// that would wrap past the bottom are clipped (not drawn) rather than wrapping.
+    pub schip_collision: bool,
+    /// Wrap sprite columns that run off the right edge back around to the left,
+    /// instead of clipping them. This is the native default; disable to reproduce
+    /// a ROM that expects horizontal clipping.
+    pub wrap_x: bool,
+    /// Wrap sprite rows that run off the bottom edge back around to the top,
+    /// instead of clipping them. This is the native default; disable to reproduce
+    /// a ROM that expects vertical clipping. Forced off when `schip_collision` is set.
+    pub wrap_y: bool,
+    /// The CHIP-8 spec only defines `5xy0`/`9xy0`; other `5xy_`/`9xy_` forms are
+    /// undefined. Most interpreters silently no-op them, which is what this
+    /// emulator does by default. Enable to instead raise
+    /// [`crate::Chip8Error::UnknownOpcode`] on those forms, for ROM validation
+    /// or catching a malformed program that stumbled into one by accident.
+    pub strict_undefined_forms: bool,
+    /// Which held key `Fx0A` stores when multiple keys are pressed at once.
+    pub fx0a_key_order: Fx0aKeyOrder,
+    /// Whether the arithmetic/shift `8xy_` opcodes write VF or their destination
+    /// register last, when that destination happens to be VF itself.
+    pub flag_write_order: FlagWriteOrder,
+    /// Which register `8xy6`/`8xyE` shift: `Vx` in place, or `Vy` into `Vx`.
+    pub shift_quirk: ShiftQuirk,
+    /// Whether `Fx55`/`Fx65` leave `I` unchanged or advance it afterward.
+    pub load_store_quirk: LoadStoreQuirk,
+    /// On some hardware the buzzer doesn't audibly sound until the sound timer
+    /// is at least 2; a value of 1 counts down silently. This emulator's
+    /// [`crate::Chip8::is_beeping`] treats any nonzero timer as beeping by
+    /// default; enable to require at least 2 instead.
+    pub quiet_beep_at_one: bool,
+    /// What `CALL` does when the call stack is already full.
+    pub stack_overflow_behavior: StackOverflowBehavior,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self {
+            logic_reset_vf: false,
+            allow_sys_noop: false,
+            schip_collision: false,
+            wrap_x: true,
+            wrap_y: true,
+            strict_undefined_forms: false,
+            fx0a_key_order: Fx0aKeyOrder::LowestIndex,
+            flag_write_order: FlagWriteOrder::FlagLast,
+            shift_quirk: ShiftQuirk::UseVx,
+            load_store_quirk: LoadStoreQuirk::Unchanged,
+            quiet_beep_at_one: false,
+            stack_overflow_behavior: StackOverflowBehavior::Error,
+        }
+    }
+}
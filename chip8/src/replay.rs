@@ -0,0 +1,12 @@
+//! The bundle of data needed to deterministically reproduce a recorded input
+//! session. See [`crate::Chip8::save_replay_to_path`].
+
+/// A recorded input session, together with the RNG seed and ROM identity needed
+/// to reproduce it exactly: construct a fresh [`crate::Chip8`] with
+/// [`crate::Chip8::with_seed`] using [`Self::seed`], load the same ROM, then
+/// feed [`Self::input_log`] to [`crate::Chip8::replay_input`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub seed: u64,
+    pub input_log: Vec<(u64, usize, bool)>,
+}
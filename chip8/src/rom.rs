@@ -0,0 +1,145 @@
+//! ROM inspection helpers that don't require a running [`crate::Chip8`].
+
+use crate::disasm::is_known_opcode;
+use crate::QuirksPreset;
+
+const START_ADDR: u16 = 0x200;
+
+/// Scan a ROM's opcodes for platform-specific instructions and guess which
+/// [`QuirksPreset`] it was written for, defaulting to `None` (classic) if no
+/// marker opcode is found.
+pub fn detect_platform(rom: &[u8]) -> Option<QuirksPreset> {
+    let mut best: Option<QuirksPreset> = None;
+
+    for pair in rom.chunks_exact(2) {
+        let opcode = u16::from_be_bytes([pair[0], pair[1]]);
+        let last_nibble = opcode & 0x000F;
+
+        // 00FF - high-res mode, 00Cn - scroll down, Dxy0 - clipped 16x16 draw,
+        // Fx30 - large font, Fx75/Fx85 - save/load RPL flags.
+        let is_superchip = opcode == 0x00FF
+            || (opcode & 0xFFF0) == 0x00C0
+            || (opcode & 0xF00F) == 0xD000
+            || (opcode & 0xF0FF) == 0xF030
+            || (opcode & 0xF0FF) == 0xF075
+            || (opcode & 0xF0FF) == 0xF085;
+
+        // F000 nnnn - assign I = nnnn (a following 16-bit word), Fn01 - select bitplane n.
+        let is_xochip = opcode == 0xF000 || ((opcode & 0xF0FF) == 0xF001 && last_nibble == 0x1);
+
+        let found = if is_xochip {
+            Some(QuirksPreset::XoChip)
+        } else if is_superchip {
+            Some(QuirksPreset::SuperChip)
+        } else {
+            None
+        };
+
+        best = match (best, found) {
+            (Some(current), Some(candidate)) => Some(current.max(candidate)),
+            (None, Some(candidate)) => Some(candidate),
+            (current, None) => current,
+        };
+    }
+
+    best
+}
+
+/// Strip a leading Octo-style comment header - one or more lines starting with
+/// `#` - from `data`, returning just the binary ROM that follows. `data` that
+/// doesn't start with `#` is assumed to already be a plain binary ROM and is
+/// returned unchanged.
+pub fn strip_header(data: &[u8]) -> &[u8] {
+    let mut rest = data;
+    while rest.first() == Some(&b'#') {
+        let line_end = rest.iter().position(|&b| b == b'\n').map_or(rest.len(), |i| i + 1);
+        rest = &rest[line_end..];
+    }
+    rest
+}
+
+/// Walk a ROM two bytes at a time from 0x200 and report `(address, opcode)` pairs
+/// that don't decode to any known instruction. This is heuristic - data embedded
+/// in the ROM (e.g. sprites) may look like bad opcodes - so treat it as advisory.
+pub fn validate(rom: &[u8]) -> Vec<(u16, u16)> {
+    rom.chunks_exact(2)
+        .enumerate()
+        .filter_map(|(i, pair)| {
+            let opcode = u16::from_be_bytes([pair[0], pair[1]]);
+            if is_known_opcode(opcode) {
+                None
+            } else {
+                Some((START_ADDR + (i as u16) * 2, opcode))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_classic_rom_as_none() {
+        // A harmless JP loop with no platform-specific opcodes.
+        let rom = [0x12, 0x00];
+        assert_eq!(detect_platform(&rom), None);
+    }
+
+    #[test]
+    fn detects_superchip_high_res_marker() {
+        let rom = [0x00, 0xFF];
+        assert_eq!(detect_platform(&rom), Some(QuirksPreset::SuperChip));
+    }
+
+    #[test]
+    fn detects_superchip_scroll_down_marker() {
+        let rom = [0x00, 0xC5];
+        assert_eq!(detect_platform(&rom), Some(QuirksPreset::SuperChip));
+    }
+
+    #[test]
+    fn detects_superchip_clipped_draw_marker() {
+        let rom = [0xD1, 0x20];
+        assert_eq!(detect_platform(&rom), Some(QuirksPreset::SuperChip));
+    }
+
+    #[test]
+    fn detects_superchip_large_font_and_rpl_markers() {
+        assert_eq!(detect_platform(&[0xF1, 0x30]), Some(QuirksPreset::SuperChip));
+        assert_eq!(detect_platform(&[0xF1, 0x75]), Some(QuirksPreset::SuperChip));
+        assert_eq!(detect_platform(&[0xF1, 0x85]), Some(QuirksPreset::SuperChip));
+    }
+
+    #[test]
+    fn detects_xochip_markers_and_prefers_most_advanced() {
+        let rom = [0x00, 0xFF, 0xF0, 0x00, 0xF0, 0x00];
+        assert_eq!(detect_platform(&rom), Some(QuirksPreset::XoChip));
+    }
+
+    #[test]
+    fn strip_header_removes_leading_comment_lines() {
+        let mut data = b"# title: Test ROM\n# author: someone\n".to_vec();
+        data.extend_from_slice(&[0x12, 0x00]);
+        assert_eq!(strip_header(&data), &[0x12, 0x00]);
+    }
+
+    #[test]
+    fn strip_header_leaves_plain_binary_roms_unchanged() {
+        let rom = [0x12, 0x00, 0x00, 0xE0];
+        assert_eq!(strip_header(&rom), &rom);
+    }
+
+    #[test]
+    fn validate_flags_a_known_invalid_opcode() {
+        // JP 0x204 (valid), then 0x5001 (invalid: 5xy_ requires the last nibble to be 0).
+        let rom = [0x12, 0x04, 0x50, 0x01];
+        assert_eq!(validate(&rom), vec![(0x202, 0x5001)]);
+    }
+
+    #[test]
+    fn validate_accepts_a_clean_rom() {
+        let rom = [0x60, 0x01, 0x12, 0x00];
+        assert!(validate(&rom).is_empty());
+    }
+}
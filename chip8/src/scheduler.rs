@@ -0,0 +1,123 @@
+//! A fixed-tick-rate main-loop helper, decoupling CPU execution from a
+//! frontend's variable frame render time without busy-sleeping.
+//!
+//! Frontends previously hardcoded a target FPS, ran a flat number of ticks per
+//! frame, then slept the frame's leftover time - drifting further behind
+//! schedule on any frame that runs long. [`FrameScheduler`] centralizes that
+//! bookkeeping and carries drift forward so the frontend can catch back up.
+
+use std::time::Duration;
+
+/// What a frontend should do to stay on schedule for the upcoming frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStep {
+    /// How many CPU ticks to run this frame.
+    pub ticks_to_run: u32,
+    /// How long to sleep after running those ticks and drawing.
+    pub sleep_for: Duration,
+}
+
+/// Computes how many CPU ticks to run and how long to sleep each frame to hit
+/// a target FPS, carrying drift from frames that ran over budget into the
+/// following frames instead of letting timing permanently slip.
+pub struct FrameScheduler {
+    frame_duration: Duration,
+    ticks_per_frame: u32,
+    /// Real time owed beyond a frame's budget, carried over from prior frames.
+    drift: Duration,
+}
+
+impl FrameScheduler {
+    /// `fps` is the target frame rate; `ticks_per_frame` is how many CPU ticks
+    /// a single on-schedule frame runs.
+    pub fn new(fps: u32, ticks_per_frame: u32) -> Self {
+        Self {
+            frame_duration: Duration::from_nanos(1_000_000_000 / fps as u64),
+            ticks_per_frame,
+            drift: Duration::ZERO,
+        }
+    }
+
+    /// Given how long this frame's work (events, ticks, draw) actually took,
+    /// return how many ticks to run and how long to sleep to land back on
+    /// schedule. A frame (or run of frames) that took at least a whole
+    /// `frame_duration` gets no sleep and `ticks_per_frame` ticks for every whole
+    /// `frame_duration` elapsed (so extra ticks once two or more have elapsed),
+    /// catching the simulation back up to real time; any leftover time short
+    /// of a full frame is carried forward as drift rather than lost.
+    pub fn next_step(&mut self, frame_work_duration: Duration) -> FrameStep {
+        let total = frame_work_duration + self.drift;
+        let full_frames = (total.as_nanos() / self.frame_duration.as_nanos()) as u32;
+
+        if full_frames == 0 {
+            self.drift = Duration::ZERO;
+            FrameStep {
+                ticks_to_run: self.ticks_per_frame,
+                sleep_for: self.frame_duration - total,
+            }
+        } else {
+            self.drift = total - self.frame_duration * full_frames;
+            FrameStep {
+                ticks_to_run: self.ticks_per_frame * full_frames,
+                sleep_for: Duration::ZERO,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_instant_frame_runs_the_base_tick_count_and_sleeps_the_full_frame() {
+        let mut scheduler = FrameScheduler::new(60, 10);
+        let step = scheduler.next_step(Duration::ZERO);
+        assert_eq!(step.ticks_to_run, 10);
+        assert_eq!(step.sleep_for, Duration::from_nanos(1_000_000_000 / 60));
+    }
+
+    #[test]
+    fn a_frame_using_half_the_budget_sleeps_the_other_half() {
+        let mut scheduler = FrameScheduler::new(60, 10);
+        let frame_duration = Duration::from_nanos(1_000_000_000 / 60);
+        let step = scheduler.next_step(frame_duration / 2);
+        assert_eq!(step.ticks_to_run, 10);
+        assert_eq!(step.sleep_for, frame_duration - frame_duration / 2);
+    }
+
+    #[test]
+    fn drift_from_a_slow_frame_is_repaid_by_a_shorter_sleep_next_frame() {
+        let mut scheduler = FrameScheduler::new(60, 10);
+        let frame_duration = Duration::from_nanos(1_000_000_000 / 60);
+
+        // This frame ran a bit over budget, so it doesn't sleep, and the
+        // overrun carries forward.
+        let overrun = frame_duration + Duration::from_millis(2);
+        let first = scheduler.next_step(overrun);
+        assert_eq!(first.ticks_to_run, 10);
+        assert_eq!(first.sleep_for, Duration::ZERO);
+
+        // The next, instant frame absorbs that debt out of its own sleep.
+        let second = scheduler.next_step(Duration::ZERO);
+        assert_eq!(second.ticks_to_run, 10);
+        assert_eq!(second.sleep_for, frame_duration - Duration::from_millis(2));
+    }
+
+    #[test]
+    fn falling_multiple_frames_behind_runs_extra_ticks_to_catch_up() {
+        let mut scheduler = FrameScheduler::new(60, 10);
+        let frame_duration = Duration::from_nanos(1_000_000_000 / 60);
+
+        // A stall lasting 3.5 frame windows means 3 whole frames' worth of
+        // ticks are owed right away, with no sleep, and half a frame of
+        // drift left over for the next call.
+        let step = scheduler.next_step(frame_duration * 3 + frame_duration / 2);
+        assert_eq!(step.ticks_to_run, 30);
+        assert_eq!(step.sleep_for, Duration::ZERO);
+
+        let next = scheduler.next_step(Duration::ZERO);
+        assert_eq!(next.ticks_to_run, 10);
+        assert_eq!(next.sleep_for, frame_duration - frame_duration / 2);
+    }
+}
@@ -0,0 +1,15 @@
+//! A cheap, loggable copy of the machine state, decoupled from the live [`crate::Chip8`].
+
+/// A `Clone + Debug` snapshot of the machine state, cheaper to pass around and log
+/// than the full [`crate::Chip8`] (which owns the whole RAM array).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineSnapshot {
+    pub program_counter: u16,
+    pub stack_pointer: u8,
+    pub i_register: u16,
+    pub v_registers: [u8; 16],
+    pub delay_timer: u8,
+    pub sound_timer: u8,
+    /// The display packed 8 pixels per byte, MSB-first, row-major.
+    pub packed_display: [u8; 256],
+}
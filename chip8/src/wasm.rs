@@ -0,0 +1,71 @@
+//! A thin `wasm-bindgen` wrapper around [`crate::Chip8`] for browser frontends.
+//!
+//! `wasm-bindgen` can't export the core `Chip8` type's methods directly (its
+//! `bool`/slice-returning API isn't all JS-representable), so this module wraps
+//! it in a struct with a JS-friendly surface. Kept intentionally small: add
+//! methods here as browser frontends need them.
+
+use wasm_bindgen::prelude::*;
+
+use crate::Chip8;
+
+/// A `Chip8` instance exposed to JavaScript.
+#[wasm_bindgen]
+pub struct WasmChip8(Chip8);
+
+#[wasm_bindgen]
+impl WasmChip8 {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self(Chip8::new())
+    }
+
+    pub fn load(&mut self, rom: &[u8]) {
+        self.0.load(rom).unwrap_or_else(|e| panic!("{e}"));
+    }
+
+    pub fn tick(&mut self) {
+        self.0.tick();
+    }
+
+    pub fn tick_timers(&mut self) {
+        self.0.tick_timers();
+    }
+
+    /// Whether the display has changed since the last call, cleared on read.
+    /// Lets JS skip copying the display buffer on frames where nothing moved.
+    pub fn display_changed(&mut self) -> bool {
+        self.0.take_screen_dirty()
+    }
+
+    /// The display as one byte per pixel (`0` or `1`), row-major, for JS to
+    /// copy into a canvas `ImageData` buffer.
+    pub fn get_display(&self) -> Vec<u8> {
+        self.0.get_display().iter().map(|&lit| lit as u8).collect()
+    }
+}
+
+impl Default for WasmChip8 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_changed_is_set_by_a_draw_and_cleared_after_reading() {
+        let mut chip8 = WasmChip8::new();
+        assert!(!chip8.display_changed());
+
+        chip8.0.write_ram(0x300, &[0b1111_0000]).unwrap();
+        chip8.0.set_i_register(0x300);
+        chip8.load(&[0xD0, 0x01]); // DRW V0, V0, 1
+        chip8.tick();
+
+        assert!(chip8.display_changed());
+        assert!(!chip8.display_changed()); // cleared after taking
+    }
+}
@@ -1,11 +1,18 @@
-use chip8::{Chip8, SCREEN_HEIGHT, SCREEN_WIDTH};
+use chip8::{Chip8, FrameScheduler, FrameStep, SCREEN_HEIGHT, SCREEN_WIDTH};
+use gif::{Encoder, Frame, Repeat};
 use sdl2::{
-    event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::Canvas, video::Window,
+    event::Event,
+    keyboard::Keycode,
+    pixels::{Color, PixelFormatEnum},
+    rect::Rect,
+    render::{Canvas, Texture, TextureCreator},
+    video::{Window, WindowContext},
 };
 use std::{
     env,
     fs::File,
     io::Read,
+    path::{Path, PathBuf},
     time::{Duration, Instant},
 };
 
@@ -14,15 +21,41 @@ const WINDOW_WIDTH: u32 = (SCREEN_WIDTH as u32) * SCALE;
 const WINDOW_HEIGHT: u32 = (SCREEN_HEIGHT as u32) * SCALE;
 
 const FPS: u32 = 60;
-const FRAME_DURATION: Duration = Duration::from_millis(1000 / FPS as u64);
 const INSTRUCTION_PER_FRAME: u32 = 10;
 
+const SPRITE_OVERLAY_BYTES: u16 = 15;
+const SPRITE_OVERLAY_MARGIN: u32 = 4;
+
+/// Color of the `--grid` overlay's gridlines: dim enough not to compete with
+/// the emulated pixels, since it's purely a pixel-art debugging aid.
+const GRID_COLOR: Color = Color::RGB(40, 40, 40);
+
+const HUD_FONT_WIDTH: u32 = 3;
+const HUD_FONT_HEIGHT: u32 = 5;
+const HUD_CHAR_SCALE: u32 = 3;
+const HUD_CHAR_SPACING: u32 = 1;
+const HUD_MARGIN: i32 = 4;
+
+/// GIF frame delay in centiseconds, matching [`FPS`] as closely as the GIF
+/// format's 10ms delay granularity allows (1/60s rounds to 2 centiseconds,
+/// i.e. 50 FPS rather than 60).
+const GIF_FRAME_DELAY_CENTISECONDS: u16 = 2;
+/// Cap on `--record`ed frames (10 minutes at [`FPS`]) so a forgotten recording
+/// can't grow into a huge file.
+const MAX_RECORDED_GIF_FRAMES: u32 = FPS * 60 * 10;
+
 pub fn main() {
     // File name
     let args: Vec<String> = env::args().collect();
     if args.len() < 2 {
         panic!("No filename found");
     }
+    let palette = parse_palette_arg(&args);
+    let mut paused = parse_pause_flag(&args);
+    let grid = parse_grid_flag(&args);
+    let mut gif_recorder = parse_record_arg(&args).map(|path| GifRecorder::new(&path, &palette));
+    let input_script = parse_input_script_arg(&args).map(|path| load_input_script(&path)).unwrap_or_default();
+    let mut input_script_index = 0usize;
 
     // Setup SDL
     let sdl_context = sdl2::init().unwrap();
@@ -35,15 +68,31 @@ pub fn main() {
         .unwrap();
     let mut canvas = window.into_canvas().present_vsync().build().unwrap();
 
-    // Setup emulator, reading file
+    // The display is drawn into a small streaming texture, sized to the
+    // emulator's current resolution, and blitted scaled up to fill the window
+    // (see `draw_screen`), rather than filling one `Rect` per pixel.
+    // Nearest-neighbor keeps pixel art crisp; this hint must be set before the
+    // texture is created.
+    sdl2::hint::set("SDL_RENDER_SCALE_QUALITY", "0");
+    let texture_creator = canvas.texture_creator();
+    let mut display_size = (SCREEN_WIDTH, SCREEN_HEIGHT);
+    let mut display_texture = create_display_texture(&texture_creator, display_size);
+
+    // Setup emulator, reading file(s) - a single ROM launches directly, a
+    // directory browses its `.ch8` files via PageUp/PageDown.
+    let rom_paths = enumerate_roms(Path::new(&args[1]));
+    let mut current_rom_index = 0;
     let mut chip8 = Chip8::new();
-    let mut rom = File::open(&args[1]).expect("Unable to open file");
-    let mut buffer = Vec::new();
-    rom.read_to_end(&mut buffer).unwrap();
-    chip8.load(&buffer);
+    load_rom(&mut chip8, &rom_paths[current_rom_index]);
+    canvas.window_mut().set_title(&window_title(&rom_paths[current_rom_index])).unwrap();
 
     // Game loop
     let mut event_pump = sdl_context.event_pump().unwrap();
+    let mut sprite_debug_overlay = false;
+    let mut debug_hud = false;
+    let mut scheduler = FrameScheduler::new(FPS, INSTRUCTION_PER_FRAME);
+    let mut step = FrameStep { ticks_to_run: INSTRUCTION_PER_FRAME, sleep_for: Duration::ZERO };
+    let mut frame_count: u32 = 0;
 
     'gameloop: loop {
         let frame_start = Instant::now(); // Mark the start of the frame
@@ -54,6 +103,48 @@ pub fn main() {
                 Event::Quit { .. } => {
                     break 'gameloop;
                 }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F2),
+                    ..
+                } => {
+                    sprite_debug_overlay = !sprite_debug_overlay;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::F3),
+                    ..
+                } => {
+                    debug_hud = !debug_hud;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::P),
+                    ..
+                } => {
+                    paused = !paused;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::N),
+                    ..
+                } if paused => {
+                    chip8.tick();
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::PageDown),
+                    ..
+                } if rom_paths.len() > 1 => {
+                    current_rom_index = (current_rom_index + 1) % rom_paths.len();
+                    chip8 = Chip8::new();
+                    load_rom(&mut chip8, &rom_paths[current_rom_index]);
+                    canvas.window_mut().set_title(&window_title(&rom_paths[current_rom_index])).unwrap();
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::PageUp),
+                    ..
+                } if rom_paths.len() > 1 => {
+                    current_rom_index = (current_rom_index + rom_paths.len() - 1) % rom_paths.len();
+                    chip8 = Chip8::new();
+                    load_rom(&mut chip8, &rom_paths[current_rom_index]);
+                    canvas.window_mut().set_title(&window_title(&rom_paths[current_rom_index])).unwrap();
+                }
                 Event::KeyDown {
                     keycode: Some(key), ..
                 } => {
@@ -72,66 +163,640 @@ pub fn main() {
             }
         }
 
-        // Run emulator cycles - execute more ticks based on calculated ticks_to_run
-        for _ in 0..INSTRUCTION_PER_FRAME {
-            chip8.tick();
+        // Apply any scripted keypresses due this frame, for reproducible demos.
+        while input_script_index < input_script.len()
+            && input_script[input_script_index].frame == frame_count
+        {
+            let entry = &input_script[input_script_index];
+            chip8.keypress(entry.key, entry.pressed);
+            input_script_index += 1;
         }
-        chip8.tick_timers();
 
-        // Draw the screen
-        draw_screen(&mut chip8, &mut canvas);
+        // Run emulator cycles - execute more ticks based on calculated ticks_to_run.
+        // While paused, N (handled above) single-steps instead.
+        if !paused {
+            for _ in 0..step.ticks_to_run {
+                chip8.tick();
+            }
+            chip8.tick_timers();
+        }
 
-        // Calculate how long the frame took
-        let frame_duration = frame_start.elapsed();
+        // A SUPER-CHIP resolution switch (00FE/00FF) changes the display's pixel
+        // dimensions, so the streaming texture must be recreated to match before
+        // this frame's pixels are uploaded into it.
+        let current_size = (chip8.display_width(), chip8.display_height());
+        if current_size != display_size {
+            display_size = current_size;
+            display_texture = create_display_texture(&texture_creator, display_size);
+        }
 
-        // If the frame took less time than the target frame duration, sleep for the remaining time
-        if frame_duration < FRAME_DURATION {
-            std::thread::sleep(FRAME_DURATION - frame_duration);
+        // Draw the screen
+        draw_screen(
+            &mut chip8,
+            &mut canvas,
+            &mut display_texture,
+            sprite_debug_overlay,
+            debug_hud,
+            grid,
+            &palette,
+        );
+        if let Some(recorder) = &mut gif_recorder {
+            recorder.record_frame(&chip8);
         }
+
+        // Ask the scheduler how many ticks to run next frame and how long to
+        // sleep now, based on how long this frame's work actually took.
+        step = scheduler.next_step(frame_start.elapsed());
+        std::thread::sleep(step.sleep_for);
+        frame_count += 1;
     }
 }
 
-fn draw_screen(chip: &mut Chip8, canvas: &mut Canvas<Window>) {
-    // Clear the canvas with black color
-    canvas.set_draw_color(Color::RGB(0, 0, 0));
-    canvas.clear();
+/// Create the streaming texture [`draw_screen`] paints the display into, sized
+/// to `(width, height)` - the emulator's current [`Chip8::display_width`]/
+/// [`Chip8::display_height`]. Recreated by the game loop whenever a
+/// resolution switch changes those dimensions.
+fn create_display_texture(
+    texture_creator: &TextureCreator<WindowContext>,
+    (width, height): (usize, usize),
+) -> Texture<'_> {
+    texture_creator
+        .create_texture_streaming(PixelFormatEnum::RGB24, width as u32, height as u32)
+        .unwrap()
+}
 
-    let screen_buf = chip.get_display();
-    canvas.set_draw_color(Color::RGB(255, 255, 255));
+fn draw_screen(
+    chip: &mut Chip8,
+    canvas: &mut Canvas<Window>,
+    texture: &mut Texture,
+    sprite_debug_overlay: bool,
+    debug_hud: bool,
+    grid: bool,
+    palette: &[Color],
+) {
+    // Paint the CHIP-8 screen into a small streaming texture (sized to the
+    // current display resolution by `create_display_texture`), then blit it
+    // scaled up to fill the window. The GPU does the scaling (nearest-neighbor,
+    // set via the SDL_RENDER_SCALE_QUALITY hint in `main`) instead of one
+    // `fill_rect` per emulated pixel, which stays cheap regardless of window
+    // size and leaves room for future shader-based post-processing on the same
+    // texture.
+    let width = chip.display_width();
+    let height = chip.display_height();
+    texture.update(None, &display_rgb24_bytes(chip, palette), width * 3).unwrap();
+    canvas.copy(texture, None, None).unwrap();
 
-    // Draw each pixel from the CHIP-8 screen buffer
-    for (i, &pixel) in screen_buf.iter().enumerate() {
-        if pixel {
-            let x = (i % SCREEN_WIDTH) as u32;
-            let y = (i / SCREEN_WIDTH) as u32;
+    if grid {
+        draw_pixel_grid(canvas, width, height);
+    }
 
-            let rect = Rect::new((x * SCALE) as i32, (y * SCALE) as i32, SCALE, SCALE);
-            canvas.fill_rect(rect).unwrap();
-        }
+    if sprite_debug_overlay {
+        draw_sprite_at_cursor_overlay(chip, canvas);
+    }
+
+    if debug_hud {
+        draw_debug_hud(chip, canvas);
     }
 
     // Present the canvas to the screen
     canvas.present();
 }
 
+/// The current display as a buffer of packed RGB24 bytes, one pixel's `[r, g,
+/// b]` at a time in row-major order, for uploading into [`draw_screen`]'s
+/// streaming texture. Unlit pixels (both bitplanes clear) render as black,
+/// matching the canvas's previous cleared background color.
+fn display_rgb24_bytes(chip: &Chip8, palette: &[Color]) -> Vec<u8> {
+    let plane0 = chip.display_plane(0);
+    let plane1 = chip.display_plane(1);
+    let mut bytes = Vec::with_capacity(plane0.len() * 3);
+    for i in 0..plane0.len() {
+        let color = pixel_color(plane0[i], plane1[i], palette).unwrap_or(Color::RGB(0, 0, 0));
+        bytes.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+    bytes
+}
+
+/// The color to draw a pixel given whether each bitplane is lit there, or
+/// `None` if both planes are clear (background, left as the cleared canvas
+/// color). With a full four-color `palette` the two plane bits select one of
+/// its four entries (`00`/`01`/`10`/`11`); with fewer colors this collapses to
+/// the original two-color scheme (any lit plane draws `palette[1]`).
+fn pixel_color(plane0_lit: bool, plane1_lit: bool, palette: &[Color]) -> Option<Color> {
+    let index = (plane0_lit as usize) | ((plane1_lit as usize) << 1);
+    if index == 0 {
+        return None;
+    }
+    if palette.len() >= 4 {
+        Some(palette[index])
+    } else {
+        Some(*palette.get(1).unwrap_or(&Color::RGB(255, 255, 255)))
+    }
+}
+
+/// The default two-color palette: black background, white foreground.
+fn default_palette() -> Vec<Color> {
+    vec![Color::RGB(0, 0, 0), Color::RGB(255, 255, 255)]
+}
+
+/// Read a `--palette RRGGBB,RRGGBB,...` argument (up to four entries) from the
+/// command line, falling back to [`default_palette`] when absent or unparsable.
+fn parse_palette_arg(args: &[String]) -> Vec<Color> {
+    args.iter()
+        .position(|arg| arg == "--palette")
+        .and_then(|i| args.get(i + 1))
+        .map(|list| parse_palette(list))
+        .filter(|colors| !colors.is_empty())
+        .unwrap_or_else(default_palette)
+}
+
+/// Read a `--pause` flag from the command line: start the emulator paused at
+/// the ROM's entry point instead of running immediately, so a debugging
+/// session can single-step (`N`) from instruction zero. Absent by default.
+fn parse_pause_flag(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--pause")
+}
+
+/// A single ROM launches directly; a directory is browsed as a sorted list of
+/// its `.ch8` files via `PageUp`/`PageDown`. Panics if a directory has none.
+fn enumerate_roms(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut roms: Vec<PathBuf> = std::fs::read_dir(path)
+        .expect("Unable to read ROM directory")
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("ch8")))
+        .collect();
+    roms.sort();
+    assert!(!roms.is_empty(), "No .ch8 files found in {}", path.display());
+    roms
+}
+
+/// Load the ROM at `path` into `chip`, for the initial ROM and every
+/// `PageUp`/`PageDown` switch. This doesn't reset `chip`; callers pass a fresh one.
+fn load_rom(chip: &mut Chip8, path: &Path) {
+    let mut rom = File::open(path).expect("Unable to open file");
+    let mut buffer = Vec::new();
+    rom.read_to_end(&mut buffer).unwrap();
+    chip.load(&buffer).expect("ROM file is empty");
+}
+
+/// Window title showing the currently loaded ROM's file name.
+fn window_title(path: &Path) -> String {
+    format!("Chip-8 Emulator - {}", path.file_name().unwrap_or_default().to_string_lossy())
+}
+
+/// Read a `--grid` flag from the command line: overlay faint gridlines between
+/// scaled-up pixels for pixel-art debugging. Purely cosmetic - has no effect
+/// on the emulated display. Absent by default.
+fn parse_grid_flag(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--grid")
+}
+
+/// One scripted keypress from a `--input-script` file, applied once the game
+/// loop's frame counter reaches `frame`.
+struct ScriptedInput {
+    frame: u32,
+    key: usize,
+    pressed: bool,
+}
+
+/// Read a `--input-script <file>` argument from the command line: apply
+/// scripted keypresses at specific frames, for reproducible demos (pair with
+/// a seeded [`Chip8`] to make the whole run deterministic). Absent by default.
+fn parse_input_script_arg(args: &[String]) -> Option<PathBuf> {
+    args.iter().position(|arg| arg == "--input-script").and_then(|i| args.get(i + 1)).map(PathBuf::from)
+}
+
+/// Load a `--input-script` file: one `frame key down|up` entry per line, e.g.
+/// `30 q down`, sorted by frame. A line that doesn't parse is skipped with a
+/// warning printed to stderr rather than aborting the whole script.
+fn load_input_script(path: &Path) -> Vec<ScriptedInput> {
+    let text = std::fs::read_to_string(path).expect("Unable to read input script");
+    let mut entries: Vec<ScriptedInput> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match parse_input_script_line(line) {
+            Some(entry) => Some(entry),
+            None => {
+                eprintln!("input script: skipping unrecognized line: {line:?}");
+                None
+            }
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.frame);
+    entries
+}
+
+/// Parse one `--input-script` line: `frame key down|up`, where `key` is the
+/// same QWERTY character [`get_key_button`] maps to the hex keypad.
+fn parse_input_script_line(line: &str) -> Option<ScriptedInput> {
+    let mut parts = line.split_whitespace();
+    let frame = parts.next()?.parse().ok()?;
+    let key = chip8::keymap::qwerty_to_key(parts.next()?.chars().next()?)?;
+    let pressed = match parts.next()? {
+        "down" => true,
+        "up" => false,
+        _ => return None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ScriptedInput { frame, key, pressed })
+}
+
+/// Read a `--record output.gif` argument from the command line: capture the
+/// display to an animated GIF at `path`, up to [`MAX_RECORDED_GIF_FRAMES`].
+/// Absent by default.
+fn parse_record_arg(args: &[String]) -> Option<PathBuf> {
+    args.iter().position(|arg| arg == "--record").and_then(|i| args.get(i + 1)).map(PathBuf::from)
+}
+
+/// The color each of the four bitplane-combination indices renders as, in the
+/// same `00`/`01`/`10`/`11` order [`pixel_color`] uses - index 0 is always the
+/// canvas's cleared background color rather than `palette[0]`, since
+/// [`display_rgb24_bytes`] renders unlit pixels black regardless of the
+/// configured palette.
+fn gif_palette(palette: &[Color]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(4 * 3);
+    bytes.extend_from_slice(&[0, 0, 0]);
+    for index in 1..4u8 {
+        let color = pixel_color(index & 1 != 0, index & 2 != 0, palette).unwrap();
+        bytes.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+    bytes
+}
+
+/// The current display as a buffer of [`gif_palette`] indices, one per pixel.
+fn display_gif_indices(chip: &Chip8) -> Vec<u8> {
+    let plane0 = chip.display_plane(0);
+    let plane1 = chip.display_plane(1);
+    (0..plane0.len()).map(|i| (plane0[i] as u8) | ((plane1[i] as u8) << 1)).collect()
+}
+
+/// Captures the emulated display into a looping animated GIF, one frame per
+/// [`GifRecorder::record_frame`] call, up to [`MAX_RECORDED_GIF_FRAMES`].
+/// Closes the GIF cleanly (writes the trailer) when dropped, so simply
+/// letting it go out of scope on quit is enough.
+struct GifRecorder {
+    encoder: Encoder<File>,
+    frames_written: u32,
+}
+
+impl GifRecorder {
+    /// Start recording to `path`, palettizing frames against the same
+    /// `palette` the live display uses.
+    fn new(path: &Path, palette: &[Color]) -> Self {
+        let file = File::create(path).expect("Unable to create GIF output file");
+        let mut encoder =
+            Encoder::new(file, SCREEN_WIDTH as u16, SCREEN_HEIGHT as u16, &gif_palette(palette))
+                .expect("Unable to start GIF encoder");
+        encoder.set_repeat(Repeat::Infinite).unwrap();
+        Self { encoder, frames_written: 0 }
+    }
+
+    /// Capture `chip`'s current display as one GIF frame. A no-op once
+    /// [`MAX_RECORDED_GIF_FRAMES`] have been captured, or while `chip` is in
+    /// SUPER-CHIP hi-res mode - the GIF encoder was opened at the fixed
+    /// [`SCREEN_WIDTH`] x [`SCREEN_HEIGHT`] low-res size, so a resolution
+    /// switch just pauses recording instead of writing a mismatched frame.
+    fn record_frame(&mut self, chip: &Chip8) {
+        if self.frames_written >= MAX_RECORDED_GIF_FRAMES || chip.is_hi_res() {
+            return;
+        }
+        let mut frame = Frame::from_indexed_pixels(
+            SCREEN_WIDTH as u16,
+            SCREEN_HEIGHT as u16,
+            display_gif_indices(chip),
+            None,
+        );
+        frame.delay = GIF_FRAME_DELAY_CENTISECONDS;
+        self.encoder.write_frame(&frame).expect("Unable to write GIF frame");
+        self.frames_written += 1;
+    }
+}
+
+/// Parse a comma-separated list of `RRGGBB` hex colors, keeping only the
+/// first four and skipping any entry that doesn't parse.
+fn parse_palette(list: &str) -> Vec<Color> {
+    list.split(',').filter_map(parse_hex_color).take(4).collect()
+}
+
+/// Parse a single `RRGGBB` hex color, e.g. `"ff8800"`.
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let hex = hex.trim();
+    if hex.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
+    Some(Color::RGB(r, g, b))
+}
+
+/// `--grid` overlay: draw a 1px line at each scaled cell boundary, after the
+/// pixels are filled, purely as a pixel-art debugging aid. Never touches the
+/// emulated display. Cell boundaries are computed from the window's fixed
+/// size divided by the display's current `width`/`height` rather than
+/// [`SCALE`], since a SUPER-CHIP resolution switch changes how many display
+/// pixels the (unchanged) window is stretched across.
+fn draw_pixel_grid(canvas: &mut Canvas<Window>, width: usize, height: usize) {
+    canvas.set_draw_color(GRID_COLOR);
+    for x in 0..=width {
+        let px = (x * WINDOW_WIDTH as usize / width) as i32;
+        canvas.draw_line((px, 0), (px, WINDOW_HEIGHT as i32)).unwrap();
+    }
+    for y in 0..=height {
+        let py = (y * WINDOW_HEIGHT as usize / height) as i32;
+        canvas.draw_line((0, py), (WINDOW_WIDTH as i32, py)).unwrap();
+    }
+}
+
+/// Debug overlay (toggled by F2): preview the sprite `I` currently points at,
+/// drawn in the top-right corner in a distinct color. Purely visual - it reads
+/// `i_register`/`ram` via the core accessors and never touches the emulated screen.
+fn draw_sprite_at_cursor_overlay(chip: &Chip8, canvas: &mut Canvas<Window>) {
+    let addr = chip.i_register();
+    let Ok(sprite) = chip.read_ram(addr, SPRITE_OVERLAY_BYTES as usize) else {
+        return;
+    };
+
+    canvas.set_draw_color(Color::RGB(255, 0, 255));
+    for (row, &byte) in sprite.iter().enumerate() {
+        for col in 0..8 {
+            if byte & (0x80 >> col) != 0 {
+                let x = WINDOW_WIDTH - (8 - col as u32) * SCALE - SPRITE_OVERLAY_MARGIN;
+                let y = SPRITE_OVERLAY_MARGIN + row as u32 * SCALE;
+                canvas.fill_rect(Rect::new(x as i32, y as i32, SCALE, SCALE)).unwrap();
+            }
+        }
+    }
+}
+
+/// Debug HUD (toggled by F3): the program counter, `I`, and V0..V3 in the
+/// top-left corner. Rendered with [`hud_glyph`]'s built-in bitmap font instead
+/// of `sdl2::ttf`, so the HUD doesn't need an extra font-rendering dependency
+/// (and the base build stays free of it) for a handful of hex digits.
+fn draw_debug_hud(chip: &Chip8, canvas: &mut Canvas<Window>) {
+    let v = chip.v_registers();
+    let lines = [
+        format!("PC:{:04X}", chip.program_counter()),
+        format!("I:{:04X}", chip.i_register()),
+        format!("V0:{:02X} V1:{:02X} V2:{:02X} V3:{:02X}", v[0], v[1], v[2], v[3]),
+    ];
+
+    canvas.set_draw_color(Color::RGB(0, 255, 0));
+    let line_height = (HUD_FONT_HEIGHT * HUD_CHAR_SCALE) as i32 + HUD_MARGIN;
+    for (row, line) in lines.iter().enumerate() {
+        draw_hud_text(canvas, line, HUD_MARGIN, HUD_MARGIN + row as i32 * line_height);
+    }
+}
+
+/// Draw `text` with its top-left corner at `(x, y)`, one [`hud_glyph`] per
+/// character; characters with no glyph (anything but the HUD's hex digits,
+/// labels, and `:`) are skipped.
+fn draw_hud_text(canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32) {
+    let advance = ((HUD_FONT_WIDTH + HUD_CHAR_SPACING) * HUD_CHAR_SCALE) as i32;
+    for (i, c) in text.chars().enumerate() {
+        let Some(glyph) = hud_glyph(c) else { continue };
+        let char_x = x + i as i32 * advance;
+        for (row, bits) in glyph.iter().enumerate() {
+            for col in 0..HUD_FONT_WIDTH {
+                if bits & (1u8 << (HUD_FONT_WIDTH - 1 - col)) == 0 {
+                    continue;
+                }
+                let px = char_x + (col * HUD_CHAR_SCALE) as i32;
+                let py = y + row as i32 * HUD_CHAR_SCALE as i32;
+                canvas.fill_rect(Rect::new(px, py, HUD_CHAR_SCALE, HUD_CHAR_SCALE)).unwrap();
+            }
+        }
+    }
+}
+
+/// A tiny built-in 3x5 bitmap font covering just what the debug HUD needs: hex
+/// digits, the labels `PC`/`I`/`V`, `:`, and space. Each row's low
+/// [`HUD_FONT_WIDTH`] bits are pixels, MSB-first (leftmost column first).
+fn hud_glyph(c: char) -> Option<[u8; HUD_FONT_HEIGHT as usize]> {
+    Some(match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b111, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b111, 0b100, 0b100, 0b100, 0b111],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b111, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b111, 0b100, 0b100],
+        'P' => [0b111, 0b101, 0b111, 0b100, 0b100],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        ' ' => [0b000, 0b000, 0b000, 0b000, 0b000],
+        _ => return None,
+    })
+}
+
 fn get_key_button(key: Keycode) -> Option<usize> {
-    match key {
-        Keycode::Num1 => Some(0x1),
-        Keycode::Num2 => Some(0x2),
-        Keycode::Num3 => Some(0x3),
-        Keycode::Num4 => Some(0xC),
-        Keycode::Q => Some(0x4),
-        Keycode::W => Some(0x5),
-        Keycode::E => Some(0x6),
-        Keycode::R => Some(0xD),
-        Keycode::A => Some(0x7),
-        Keycode::S => Some(0x8),
-        Keycode::D => Some(0x9),
-        Keycode::F => Some(0xE),
-        Keycode::Z => Some(0xA),
-        Keycode::X => Some(0x0),
-        Keycode::C => Some(0xB),
-        Keycode::V => Some(0xF),
-        _ => None,
+    let c = match key {
+        Keycode::Num1 => '1',
+        Keycode::Num2 => '2',
+        Keycode::Num3 => '3',
+        Keycode::Num4 => '4',
+        Keycode::Q => 'q',
+        Keycode::W => 'w',
+        Keycode::E => 'e',
+        Keycode::R => 'r',
+        Keycode::A => 'a',
+        Keycode::S => 's',
+        Keycode::D => 'd',
+        Keycode::F => 'f',
+        Keycode::Z => 'z',
+        Keycode::X => 'x',
+        Keycode::C => 'c',
+        Keycode::V => 'v',
+        _ => return None,
+    };
+    chip8::keymap::qwerty_to_key(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enumerate_roms_passes_through_a_single_file_unchanged() {
+        let path = Path::new("some_rom.ch8");
+        assert_eq!(enumerate_roms(path), vec![path.to_path_buf()]);
+    }
+
+    #[test]
+    fn enumerate_roms_lists_ch8_files_in_a_directory_sorted() {
+        let dir = std::env::temp_dir().join(format!("chip8_rom_browser_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.ch8"), []).unwrap();
+        std::fs::write(dir.join("a.ch8"), []).unwrap();
+        std::fs::write(dir.join("notes.txt"), []).unwrap();
+
+        let roms = enumerate_roms(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(roms, vec![dir.join("a.ch8"), dir.join("b.ch8")]);
+    }
+
+    #[test]
+    fn window_title_shows_the_rom_file_name() {
+        assert_eq!(window_title(Path::new("roms/pong.ch8")), "Chip-8 Emulator - pong.ch8");
+    }
+
+    #[test]
+    fn parse_palette_arg_reads_up_to_four_colors_after_the_flag() {
+        let args: Vec<String> = ["chip8", "rom.ch8", "--palette", "000000,ffffff,ff0000,00ff00"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            parse_palette_arg(&args),
+            vec![
+                Color::RGB(0, 0, 0),
+                Color::RGB(255, 255, 255),
+                Color::RGB(255, 0, 0),
+                Color::RGB(0, 255, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_palette_arg_falls_back_to_default_when_absent_or_unparsable() {
+        let no_flag: Vec<String> = ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_palette_arg(&no_flag), default_palette());
+
+        let bad_value: Vec<String> = ["chip8", "rom.ch8", "--palette", "nope"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(parse_palette_arg(&bad_value), default_palette());
+    }
+
+    #[test]
+    fn parse_pause_flag_detects_the_flag_and_defaults_to_false() {
+        let with_flag: Vec<String> =
+            ["chip8", "rom.ch8", "--pause"].iter().map(|s| s.to_string()).collect();
+        assert!(parse_pause_flag(&with_flag));
+
+        let without_flag: Vec<String> =
+            ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert!(!parse_pause_flag(&without_flag));
+    }
+
+    #[test]
+    fn parse_grid_flag_detects_the_flag_and_defaults_to_false() {
+        let with_flag: Vec<String> =
+            ["chip8", "rom.ch8", "--grid"].iter().map(|s| s.to_string()).collect();
+        assert!(parse_grid_flag(&with_flag));
+
+        let without_flag: Vec<String> =
+            ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert!(!parse_grid_flag(&without_flag));
+    }
+
+    #[test]
+    fn parse_input_script_arg_reads_the_path_after_the_flag_and_defaults_to_none() {
+        let with_flag: Vec<String> = ["chip8", "rom.ch8", "--input-script", "demo.txt"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(parse_input_script_arg(&with_flag), Some(PathBuf::from("demo.txt")));
+
+        let without_flag: Vec<String> =
+            ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_input_script_arg(&without_flag), None);
+    }
+
+    #[test]
+    fn parse_input_script_line_reads_frame_key_and_direction() {
+        let entry = parse_input_script_line("30 q down").unwrap();
+        assert_eq!(entry.frame, 30);
+        assert_eq!(entry.key, chip8::keymap::qwerty_to_key('q').unwrap());
+        assert!(entry.pressed);
+
+        let entry = parse_input_script_line("45 q up").unwrap();
+        assert_eq!(entry.frame, 45);
+        assert!(!entry.pressed);
+    }
+
+    #[test]
+    fn parse_input_script_line_rejects_unrecognized_lines() {
+        assert!(parse_input_script_line("not a valid line").is_none());
+        assert!(parse_input_script_line("30 u down").is_none()); // 'u' isn't a mapped key
+        assert!(parse_input_script_line("30 q sideways").is_none());
+    }
+
+    #[test]
+    fn load_input_script_sorts_by_frame_and_skips_bad_lines() {
+        let dir = std::env::temp_dir().join(format!("chip8_input_script_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("script.txt");
+        std::fs::write(&path, "10 w down\nbogus line\n5 q down\n10 w up\n").unwrap();
+
+        let script = load_input_script(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(script.len(), 3);
+        assert_eq!(script[0].frame, 5);
+        assert_eq!(script[1].frame, 10);
+        assert_eq!(script[2].frame, 10);
+    }
+
+    #[test]
+    fn parse_record_arg_reads_the_path_after_the_flag_and_defaults_to_none() {
+        let with_flag: Vec<String> = ["chip8", "rom.ch8", "--record", "out.gif"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(parse_record_arg(&with_flag), Some(PathBuf::from("out.gif")));
+
+        let without_flag: Vec<String> =
+            ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(parse_record_arg(&without_flag), None);
+    }
+
+    #[test]
+    fn gif_palette_uses_black_background_and_pixel_color_for_lit_indices() {
+        let palette = default_palette();
+        assert_eq!(
+            gif_palette(&palette),
+            vec![0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn pixel_color_uses_all_four_palette_entries_with_a_full_palette() {
+        let palette = [
+            Color::RGB(0, 0, 0),
+            Color::RGB(255, 0, 0),
+            Color::RGB(0, 255, 0),
+            Color::RGB(0, 0, 255),
+        ];
+        assert_eq!(pixel_color(false, false, &palette), None);
+        assert_eq!(pixel_color(true, false, &palette), Some(Color::RGB(255, 0, 0)));
+        assert_eq!(pixel_color(false, true, &palette), Some(Color::RGB(0, 255, 0)));
+        assert_eq!(pixel_color(true, true, &palette), Some(Color::RGB(0, 0, 255)));
+    }
+
+    #[test]
+    fn pixel_color_collapses_to_two_colors_with_fewer_palette_entries() {
+        let palette = default_palette();
+        assert_eq!(pixel_color(false, false, &palette), None);
+        assert_eq!(pixel_color(true, false, &palette), Some(Color::RGB(255, 255, 255)));
+        assert_eq!(pixel_color(false, true, &palette), Some(Color::RGB(255, 255, 255)));
     }
 }
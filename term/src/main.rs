@@ -0,0 +1,152 @@
+use chip8::{Chip8, FrameScheduler, FrameStep};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, Clear, ClearType};
+use crossterm::{execute, queue};
+use std::env;
+use std::fs;
+use std::io::{stdout, Stdout, Write};
+use std::time::{Duration, Instant};
+
+const FPS: u32 = 60;
+const INSTRUCTION_PER_FRAME: u32 = 10;
+
+pub fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        panic!("No filename found");
+    }
+
+    let mut chip8 = Chip8::new();
+    let rom = fs::read(&args[1]).expect("Unable to open file");
+    chip8.load(&rom).expect("ROM file is empty");
+    let mut paused = parse_pause_flag(&args);
+
+    let mut stdout = stdout();
+    enable_raw_mode().unwrap();
+    execute!(stdout, Hide, Clear(ClearType::All)).unwrap();
+
+    let mut scheduler = FrameScheduler::new(FPS, INSTRUCTION_PER_FRAME);
+    let mut step = FrameStep { ticks_to_run: INSTRUCTION_PER_FRAME, sleep_for: Duration::ZERO };
+
+    'gameloop: loop {
+        let frame_start = Instant::now();
+
+        // Most terminals only report key-down events, so each frame starts with
+        // every key released and re-presses whatever came in since the last frame.
+        let mut keys = [false; 16];
+        while event::poll(Duration::ZERO).unwrap() {
+            if let Event::Key(key_event) = event::read().unwrap() {
+                match key_event.code {
+                    KeyCode::Esc => break 'gameloop,
+                    KeyCode::Char('p') => paused = !paused,
+                    KeyCode::Char('n') if paused => {
+                        chip8.tick();
+                    }
+                    KeyCode::Char('N') if paused => {
+                        let _ = chip8.step_frame(step.ticks_to_run as usize);
+                    }
+                    _ => {
+                        if let Some(key) = get_key_button(key_event.code) {
+                            keys[key] = true;
+                        }
+                    }
+                }
+            }
+        }
+        chip8.set_keys(keys);
+
+        // While paused, 'n'/'N' (handled above) single-step or frame-step instead.
+        if !paused {
+            run_frame(&mut chip8, step.ticks_to_run);
+        }
+        draw_screen(&mut chip8, &mut stdout).unwrap();
+
+        step = scheduler.next_step(frame_start.elapsed());
+        std::thread::sleep(step.sleep_for);
+    }
+
+    execute!(stdout, Show, ResetColor, Clear(ClearType::All)).unwrap();
+    disable_raw_mode().unwrap();
+}
+
+/// Run one frame's worth of ticks. Pulled out of `main` so it can be exercised
+/// headlessly (no terminal, no raw mode) in tests.
+fn run_frame(chip8: &mut Chip8, ticks: u32) {
+    for _ in 0..ticks {
+        chip8.tick();
+    }
+    chip8.tick_timers();
+}
+
+/// Render the display two rows at a time using half-block characters: the
+/// foreground color carries the top pixel, the background color the bottom one.
+///
+/// Recomputes its dimensions from [`Chip8::display_width`]/[`Chip8::display_height`]
+/// every call so a SUPER-CHIP resolution switch takes effect on the next frame
+/// instead of needing a restart.
+fn draw_screen(chip: &mut Chip8, stdout: &mut Stdout) -> std::io::Result<()> {
+    let width = chip.display_width();
+    let height = chip.display_height();
+    let display = chip.get_display();
+    for row in 0..(height / 2) {
+        queue!(stdout, MoveTo(0, row as u16))?;
+        for x in 0..width {
+            let top = display[x + width * (row * 2)];
+            let bottom = display[x + width * (row * 2 + 1)];
+            let fg = if top { Color::White } else { Color::Black };
+            let bg = if bottom { Color::White } else { Color::Black };
+            queue!(stdout, SetForegroundColor(fg), SetBackgroundColor(bg), Print('\u{2580}'))?;
+        }
+    }
+    queue!(stdout, ResetColor)?;
+    stdout.flush()
+}
+
+/// Read a `--pause` flag from the command line: start the emulator paused at
+/// the ROM's entry point instead of running immediately, so a debugging
+/// session can single-step (`n`) from instruction zero. Absent by default.
+fn parse_pause_flag(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--pause")
+}
+
+fn get_key_button(key: KeyCode) -> Option<usize> {
+    match key {
+        KeyCode::Char(c) => chip8::keymap::qwerty_to_key(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_frame_ticks_headlessly_without_panicking() {
+        let mut chip8 = Chip8::new();
+        chip8.load(&[0x00, 0xE0, 0x12, 0x00]).unwrap(); // CLS, JP 0x200 - loops forever without drawing
+        for _ in 0..5 {
+            run_frame(&mut chip8, INSTRUCTION_PER_FRAME);
+        }
+    }
+
+    #[test]
+    fn get_key_button_maps_the_qwerty_layout_to_the_hex_keypad() {
+        assert_eq!(get_key_button(KeyCode::Char('1')), Some(0x1));
+        assert_eq!(get_key_button(KeyCode::Char('x')), Some(0x0));
+        assert_eq!(get_key_button(KeyCode::Char('v')), Some(0xF));
+        assert_eq!(get_key_button(KeyCode::Char('u')), None);
+    }
+
+    #[test]
+    fn parse_pause_flag_detects_the_flag_and_defaults_to_false() {
+        let with_flag: Vec<String> =
+            ["chip8", "rom.ch8", "--pause"].iter().map(|s| s.to_string()).collect();
+        assert!(parse_pause_flag(&with_flag));
+
+        let without_flag: Vec<String> =
+            ["chip8", "rom.ch8"].iter().map(|s| s.to_string()).collect();
+        assert!(!parse_pause_flag(&without_flag));
+    }
+}